@@ -225,7 +225,7 @@ impl ContractInteract {
             .to(self.state.current_address())
             .gas(NumExpr("30,000,000"))
             .typed(proxy::EsdtTransferWithFeeProxy)
-            .transfer(&self.wallet_address)
+            .transfer(&self.wallet_address, OptionalValue::<ManagedBuffer<StaticApi>>::None, OptionalValue::<ManagedAddress<StaticApi>>::None)
             .payment(transactions)
             .returns(ReturnsResultUnmanaged)
             .prepare_async()
@@ -259,7 +259,7 @@ impl ContractInteract {
             .to(self.state.current_address())
             .gas(NumExpr("30,000,000"))
             .typed(proxy::EsdtTransferWithFeeProxy)
-            .transfer(&self.wallet_address)
+            .transfer(&self.wallet_address, OptionalValue::<ManagedBuffer<StaticApi>>::None, OptionalValue::<ManagedAddress<StaticApi>>::None)
             .payment(transactions)
             .returns(expected_result)
             .prepare_async()
@@ -287,7 +287,7 @@ impl ContractInteract {
             .to(self.state.current_address())
             .gas(NumExpr("30,000,000"))
             .typed(proxy::EsdtTransferWithFeeProxy)
-            .transfer(&self.wallet_address)
+            .transfer(&self.wallet_address, OptionalValue::<ManagedBuffer<StaticApi>>::None, OptionalValue::<ManagedAddress<StaticApi>>::None)
             .payment(((token, token_nonce, token_amount)))
             .returns(expected_result)
             .prepare_async()
@@ -318,7 +318,7 @@ impl ContractInteract {
             .to(self.state.current_address())
             .gas(NumExpr("30,000,000"))
             .typed(proxy::EsdtTransferWithFeeProxy)
-            .transfer(&self.wallet_address)
+            .transfer(&self.wallet_address, OptionalValue::<ManagedBuffer<StaticApi>>::None, OptionalValue::<ManagedAddress<StaticApi>>::None)
             .payment(transactions)
             .returns(expected_result)
             .prepare_async()
@@ -368,7 +368,7 @@ impl ContractInteract {
             .to(self.state.current_address())
             .gas(NumExpr("30,000,000"))
             .typed(proxy::EsdtTransferWithFeeProxy)
-            .transfer(&self.wallet_address)
+            .transfer(&self.wallet_address, OptionalValue::<ManagedBuffer<StaticApi>>::None, OptionalValue::<ManagedAddress<StaticApi>>::None)
             .payment((TokenIdentifier::from(token_id.as_str()), token_nonce, token_amount))
             .returns(ReturnsResultUnmanaged)
             .prepare_async()
@@ -389,7 +389,7 @@ impl ContractInteract {
             .to(self.state.current_address())
             .gas(NumExpr("30,000,000"))
             .typed(proxy::EsdtTransferWithFeeProxy)
-            .transfer(&self.wallet_address)
+            .transfer(&self.wallet_address, OptionalValue::<ManagedBuffer<StaticApi>>::None, OptionalValue::<ManagedAddress<StaticApi>>::None)
             .egld(token_amount)
             .returns(ExpectError(4, "EGLD transfers not allowed"))
             .prepare_async()
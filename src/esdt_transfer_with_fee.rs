@@ -1,14 +1,169 @@
 #![no_std]
 
+pub mod esdt_transfer_with_fee_proxy;
 mod fee;
+mod fee_burn;
+mod fee_credit;
+mod fee_oracle;
+mod fee_rounding;
+mod fee_stats;
+mod fee_swap;
+mod fee_sweep;
+mod loyalty;
+mod permit;
+mod rebate;
+mod recipient_policy;
+mod referral;
+mod settlement;
+mod staking_discount;
+mod token_policy;
 use fee::*;
+use permit::Permit;
+use settlement::PendingTransfer;
 
 use multiversx_sc::imports::*;
 
+// version 1: `token_fee` and `paid_fees` of the initial release, no fee tokens registry
+// version 2: fee tokens registry, no zero amounts in `paid_fees`
+// version 3: `paid_fees` map replaced by `paid_fee_tokens` and `paid_fee_amount`
+pub const STORAGE_VERSION: u32 = 3;
+
+// claiming more fee tokens at once could exceed the gas limit, the rest stay claimable
+const MAX_CLAIMED_FEE_TOKENS: usize = 100;
+
+fn is_same_token<M: ManagedTypeApi>(
+    payment: &EsdtTokenPayment<M>,
+    other: &EsdtTokenPayment<M>,
+) -> bool {
+    payment.token_identifier == other.token_identifier && payment.token_nonce == other.token_nonce
+}
+
+fn remove_index<M: ManagedTypeApi>(indexes: &mut ManagedVec<M, usize>, index: usize) {
+    if let Some(position) = indexes.find(&index) {
+        indexes.remove(position);
+    }
+}
+
+// one distinct payment for each fee amount, or `None` if they cannot all be matched
+fn match_fee_amounts<M: ManagedTypeApi>(
+    payments: &ManagedVec<M, EsdtTokenPayment<M>>,
+    candidates: &ManagedVec<M, usize>,
+    fee_amounts: &ManagedVec<M, BigUint<M>>,
+) -> Option<ManagedVec<M, usize>> {
+    let mut remaining = candidates.clone();
+    let mut matched = ManagedVec::new();
+    for fee_amount in fee_amounts.iter() {
+        let position = remaining
+            .iter()
+            .position(|index| payments.get(index).amount == *fee_amount)?;
+        matched.push(remaining.take(position));
+    }
+    Some(matched)
+}
+
+// payments of a token with a fixed fee that match the fixed fee of another payment,
+// they cover that fee instead of owing their own, like a fee payment following its token did
+fn find_fee_payments<M: ManagedTypeApi>(
+    payments: &ManagedVec<M, EsdtTokenPayment<M>>,
+    fee_options: &ManagedVec<M, ManagedVec<M, EsdtTokenPayment<M>>>,
+) -> ManagedVec<M, usize> {
+    let mut fee_payments = ManagedVec::new();
+    let mut covered = ManagedVec::<M, usize>::new();
+    for (index, payment) in payments.iter().enumerate() {
+        if fee_options.get(index).is_empty() || covered.contains(&index) {
+            continue;
+        }
+        let covered_index = (0..payments.len()).find(|other| {
+            *other != index
+                && !fee_payments.contains(other)
+                && !covered.contains(other)
+                && fee_options.get(*other).iter().any(|option| {
+                    is_same_token(&option, &payment) && option.amount == payment.amount
+                })
+        });
+        if let Some(other) = covered_index {
+            fee_payments.push(index);
+            covered.push(other);
+        }
+    }
+    fee_payments
+}
+
 #[multiversx_sc::contract]
-pub trait EsdtTransferWithFee {
+pub trait EsdtTransferWithFee:
+    fee_burn::FeeBurnModule
+    + fee_credit::FeeCreditModule
+    + fee_oracle::FeeOracleModule
+    + fee_rounding::FeeRoundingModule
+    + fee_stats::FeeStatsModule
+    + fee_swap::FeeSwapModule
+    + fee_sweep::FeeSweepModule
+    + loyalty::LoyaltyModule
+    + permit::PermitModule
+    + recipient_policy::RecipientPolicyModule
+    + rebate::RebateModule
+    + referral::ReferralModule
+    + settlement::SettlementModule
+    + staking_discount::StakingDiscountModule
+    + token_policy::TokenPolicyModule
+{
     #[init]
-    fn init(&self) {}
+    fn init(&self) {
+        self.storage_version().set(STORAGE_VERSION);
+    }
+
+    // deployments older than the fee tokens registry need the tokens having a fee as arguments
+    #[upgrade]
+    fn upgrade(&self, legacy_fee_tokens: MultiValueEncoded<TokenIdentifier>) {
+        let storage_version = self.storage_version().get();
+        require!(
+            storage_version <= STORAGE_VERSION,
+            "Cannot downgrade storage version"
+        );
+
+        if storage_version < 2 {
+            self.migrate_from_v1(legacy_fee_tokens);
+        }
+        if storage_version < 3 {
+            self.migrate_paid_fees();
+        }
+        self.storage_version().set(STORAGE_VERSION);
+    }
+
+    fn migrate_from_v1(&self, legacy_fee_tokens: MultiValueEncoded<TokenIdentifier>) {
+        for token in legacy_fee_tokens {
+            let legacy_fee_mapper = self.legacy_token_fee(&token);
+            if legacy_fee_mapper.is_empty() {
+                continue;
+            }
+            let fee = Fee::from(legacy_fee_mapper.get());
+            self.set_token_fee(&token, fee);
+        }
+    }
+
+    fn migrate_paid_fees(&self) {
+        let mut legacy_paid_fees = self.legacy_paid_fees();
+        let mut paid_fee_tokens = self.paid_fee_tokens();
+        for ((token, nonce), amount) in legacy_paid_fees.iter() {
+            // version 1 stored the percentage fees rounded down to 0, which made claiming fail
+            if amount == 0 {
+                continue;
+            }
+            self.paid_fee_amount(&token, nonce).set(amount);
+            paid_fee_tokens.insert((token, nonce));
+        }
+        legacy_paid_fees.clear();
+    }
+
+    fn set_token_fee(&self, token: &TokenIdentifier, fee: Fee<Self::Api>) {
+        self.fee_tokens().insert(token.clone());
+        // drops the remainders carried in units of the previous fee
+        let previous_fee = self.token_fee(token).get();
+        if matches!(previous_fee, Fee::Percentage(_) | Fee::Fraction(_)) {
+            self.fee_version(token).update(|version| *version += 1);
+        }
+        self.token_fee(token).set(fee);
+    }
 
     #[only_owner]
     #[endpoint(setExactValueFee)]
@@ -18,117 +173,864 @@ pub trait EsdtTransferWithFee {
         fee_amount: BigUint,
         token: TokenIdentifier,
     ) {
-        self.token_fee(&token)
-            .set(Fee::ExactValue(EsdtTokenPayment::new(
-                fee_token, 0, fee_amount,
-            )));
+        self.set_token_fee(
+            &token,
+            Fee::ExactValue(EsdtTokenPayment::new(fee_token, 0, fee_amount)),
+        );
+    }
+
+    #[only_owner]
+    #[endpoint(setExactValueFeeOptions)]
+    fn set_exact_value_fee_options(
+        &self,
+        token: TokenIdentifier,
+        fee_options: MultiValueEncoded<MultiValue2<TokenIdentifier, BigUint>>,
+    ) {
+        let mut options = ManagedVec::new();
+        for fee_option in fee_options {
+            let (fee_token, fee_amount) = fee_option.into_tuple();
+            options.push(EsdtTokenPayment::new(fee_token, 0, fee_amount));
+        }
+        require!(!options.is_empty(), "No fee options provided");
+
+        self.set_token_fee(&token, Fee::ExactValueOptions(options));
+    }
+
+    #[only_owner]
+    #[endpoint(setOraclePricedFee)]
+    fn set_oracle_priced_fee(
+        &self,
+        fee_token: TokenIdentifier,
+        fee_token_decimals: u32,
+        reference_amount: BigUint,
+        token: TokenIdentifier,
+    ) {
+        self.set_token_fee(
+            &token,
+            Fee::OraclePriced(PricedFee {
+                fee_token,
+                fee_token_decimals,
+                reference_amount,
+            }),
+        );
     }
 
     #[only_owner]
     #[endpoint(setPercentageFee)]
     fn set_percentage_fee(&self, fee: u32, token: TokenIdentifier) {
-        self.token_fee(&token).set(Fee::Percentage(fee));
+        self.set_token_fee(&token, Fee::Percentage(fee));
+    }
+
+    #[only_owner]
+    #[endpoint(setFractionFee)]
+    fn set_fraction_fee(&self, numerator: u64, denominator: u64, token: TokenIdentifier) {
+        require!(
+            denominator > 0 && numerator <= denominator,
+            "Invalid fee fraction"
+        );
+        self.set_token_fee(
+            &token,
+            Fee::Fraction(FeeFraction {
+                numerator,
+                denominator,
+            }),
+        );
+    }
+
+    #[only_owner]
+    #[endpoint(setRoyaltiesFee)]
+    fn set_royalties_fee(
+        &self,
+        fee_token: TokenIdentifier,
+        reference_amount: BigUint,
+        token: TokenIdentifier,
+    ) {
+        self.set_token_fee(
+            &token,
+            Fee::Royalties(RoyaltyFee {
+                fee_token,
+                reference_amount,
+            }),
+        );
+    }
+
+    #[only_owner]
+    #[endpoint(setFeeOverpaymentRefund)]
+    fn set_fee_overpayment_refund(&self, enabled: bool) {
+        self.fee_overpayment_refund().set(enabled);
     }
 
     #[only_owner]
     #[endpoint(claimFees)]
     fn claim_fees(&self) {
-        let paid_fees = self.paid_fees();
-        require!(!paid_fees.is_empty(), "There is nothing to claim");
+        let mut paid_fee_tokens = self.paid_fee_tokens();
+        require!(!paid_fee_tokens.is_empty(), "There is nothing to claim");
         let mut fees = ManagedVec::new();
-        for ((token, nonce), amount) in self.paid_fees().iter() {
-            fees.push(EsdtTokenPayment::new(token, nonce, amount))
+        while !paid_fee_tokens.is_empty() && fees.len() < MAX_CLAIMED_FEE_TOKENS {
+            // removing the last item does not move any other item of the set
+            let (token, nonce) = paid_fee_tokens.get_by_index(paid_fee_tokens.len());
+            paid_fee_tokens.swap_remove(&(token.clone(), nonce));
+            let amount = self.paid_fee_amount(&token, nonce).take();
+            fees.push(EsdtTokenPayment::new(token, nonce, amount));
         }
-        self.paid_fees().clear();
+        self.record_fees_claimed(&fees);
 
         self.tx().to(ToCaller).payment(&fees).transfer();
     }
 
     #[payable("*")]
     #[endpoint]
-    fn transfer(&self, address: ManagedAddress) {
+    #[allow_multiple_var_args]
+    fn transfer(
+        &self,
+        address: ManagedAddress,
+        opt_memo: OptionalValue<ManagedBuffer>,
+        opt_referrer: OptionalValue<ManagedAddress>,
+    ) {
+        require!(
+            *self.call_value().egld_value() == 0,
+            "EGLD transfers not allowed"
+        );
+        let referrer = opt_referrer
+            .into_option()
+            .filter(|referrer| self.referrers().contains(referrer));
+        let payments = self.call_value().all_esdt_transfers();
+        let memo = opt_memo.into_option().unwrap_or_default();
+        let mut refunds = ManagedVec::<Self::Api, EsdtTokenPayment>::new();
+        let caller = self.blockchain().get_caller();
+        self.execute_transfer(
+            &caller,
+            &address,
+            &payments,
+            &memo,
+            &FeeBeneficiaries {
+                sender: Some(caller.clone()),
+                referrer,
+                relayer: None,
+            },
+            &mut refunds,
+        );
+        self.tx()
+            .to(ToCaller)
+            .payment(&refunds)
+            .transfer_if_not_empty();
+    }
+
+    // the fee is taken once, the remainder of the proportional split goes to the first recipient
+    #[payable("*")]
+    #[endpoint(splitTransfer)]
+    fn split_transfer(&self, recipients: MultiValueEncoded<MultiValue2<ManagedAddress, u32>>) {
         require!(
             *self.call_value().egld_value() == 0,
             "EGLD transfers not allowed"
         );
+        let mut total_share = 0u64;
+        let mut shares = ManagedVec::<Self::Api, u32>::new();
+        let mut split_recipients = ManagedVec::<Self::Api, ManagedAddress>::new();
+        for recipient in recipients {
+            let (address, share) = recipient.into_tuple();
+            self.require_recipient_allowed(&address);
+            total_share += u64::from(share);
+            split_recipients.push(address);
+            shares.push(share);
+        }
+        require!(
+            !split_recipients.is_empty() && total_share == u64::from(PERCENTAGE_DIVISOR),
+            "Invalid split shares"
+        );
+
+        let caller = self.blockchain().get_caller();
         let payments = self.call_value().all_esdt_transfers();
+        let mut refunds = ManagedVec::<Self::Api, EsdtTokenPayment>::new();
+        let mut fees = ManagedVec::new();
+        let net_payments =
+            self.take_transfer_fees(&caller, &payments, &mut fees, &mut refunds, true);
+        require!(net_payments.len() == 1, "Only one payment can be split");
+
+        let net_payment = net_payments.get(0);
+        let mut remainder = net_payment.amount.clone();
+        let mut amounts = ManagedVec::<Self::Api, BigUint>::new();
+        for share in shares.iter() {
+            let amount = &net_payment.amount * share / PERCENTAGE_DIVISOR;
+            remainder -= &amount;
+            amounts.push(amount);
+        }
+        *amounts.get_mut(0) += remainder;
+
+        // the fees are settled together with the first transfer
+        let beneficiaries = FeeBeneficiaries {
+            sender: Some(caller.clone()),
+            referrer: None,
+            relayer: None,
+        };
+        let mut unsettled_fees = fees;
+        for (index, address) in split_recipients.iter().enumerate() {
+            let amount = amounts.get(index).clone_value();
+            if amount == 0 {
+                continue;
+            }
+            let payment = ManagedVec::from_single_item(EsdtTokenPayment::new(
+                net_payment.token_identifier.clone(),
+                net_payment.token_nonce,
+                amount,
+            ));
+            self.settle_transfer(
+                &caller,
+                &address,
+                &ManagedBuffer::new(),
+                &payment,
+                &unsettled_fees,
+                &beneficiaries,
+            );
+            unsettled_fees = ManagedVec::new();
+        }
+        self.accrue_fees(&unsettled_fees, &beneficiaries);
+        self.tx()
+            .to(ToCaller)
+            .payment(&refunds)
+            .transfer_if_not_empty();
+    }
+
+    // the relayer pays the gas, the funds and the fee come from the signer's deposit
+    #[endpoint(transferWithPermit)]
+    fn transfer_with_permit(
+        &self,
+        signer: ManagedAddress,
+        permit: Permit<Self::Api>,
+        signature: ManagedBuffer,
+    ) {
+        self.use_permit(&signer, &permit, &signature);
+
+        let fee_type = self.token_fee(&permit.payment.token_identifier).get();
+        let is_proportional_fee = matches!(fee_type, Fee::Percentage(_) | Fee::Fraction(_));
+        // a fixed fee set after signing must not be charged without the signer's consent
+        require!(
+            permit.fee.amount > 0 || is_proportional_fee || fee_type == Fee::Unset,
+            "Permit fee missing"
+        );
+        let mut payments = ManagedVec::from_single_item(permit.payment.clone());
+        if permit.fee.amount > 0 && !is_proportional_fee {
+            payments.push(permit.fee.clone());
+        }
+        for payment in payments.iter() {
+            self.debit_deposit(&signer, &payment);
+        }
+
+        let mut refunds = ManagedVec::<Self::Api, EsdtTokenPayment>::new();
+        let (forwarded, fees) = self.execute_transfer(
+            &signer,
+            &permit.recipient,
+            &payments,
+            &ManagedBuffer::new(),
+            &FeeBeneficiaries {
+                sender: Some(signer.clone()),
+                referrer: None,
+                relayer: Some(self.blockchain().get_caller()),
+            },
+            &mut refunds,
+        );
+        require!(
+            forwarded.len() == 1,
+            "Permit fee does not match the token fee"
+        );
+        if is_proportional_fee {
+            // the fee may also have been drawn from the signer's deposit
+            let mut charged_fee = BigUint::zero();
+            for fee in fees.iter() {
+                if is_same_token(&fee, &permit.payment) {
+                    charged_fee += &fee.amount;
+                }
+            }
+            require!(
+                charged_fee == 0
+                    || (is_same_token(&permit.fee, &permit.payment)
+                        && charged_fee <= permit.fee.amount),
+                "Fee above the permit maximum"
+            );
+        }
+        for refund in refunds.iter() {
+            self.credit_deposit(&signer, &refund);
+        }
+    }
+
+    // takes the fees out of the payments and forwards the rest, returns the forwarded payments and the taken fees
+    fn execute_transfer(
+        &self,
+        from: &ManagedAddress,
+        to: &ManagedAddress,
+        payments: &ManagedVec<EsdtTokenPayment>,
+        memo: &ManagedBuffer,
+        beneficiaries: &FeeBeneficiaries<Self::Api>,
+        refunds: &mut ManagedVec<EsdtTokenPayment>,
+    ) -> (ManagedVec<EsdtTokenPayment>, ManagedVec<EsdtTokenPayment>) {
+        self.require_recipient_allowed(to);
+        let mut fees = ManagedVec::new();
+        // relayed transfers only pay the fees bounded by the signed permit, never from the deposit
+        let allow_fee_credit = beneficiaries.relayer.is_none();
+        let new_payments =
+            self.take_transfer_fees(from, payments, &mut fees, refunds, allow_fee_credit);
+        require!(!new_payments.is_empty(), "No payments to transfer");
+        self.settle_transfer(from, to, memo, &new_payments, &fees, beneficiaries);
+        (new_payments, fees)
+    }
+
+    // tokens with a settlement delay are held, with their fees, until finalized or reversed
+    fn settle_transfer(
+        &self,
+        from: &ManagedAddress,
+        to: &ManagedAddress,
+        memo: &ManagedBuffer,
+        payments: &ManagedVec<EsdtTokenPayment>,
+        fees: &ManagedVec<EsdtTokenPayment>,
+        beneficiaries: &FeeBeneficiaries<Self::Api>,
+    ) {
+        let delay = self.get_settlement_delay(payments);
+        if delay == 0 {
+            self.accrue_fees(fees, beneficiaries);
+            self.transfer_event(from, to, memo, payments);
+            self.send_payments(to, payments, memo);
+            return;
+        }
+
+        self.hold_transfer(PendingTransfer {
+            from: from.clone(),
+            to: to.clone(),
+            memo: memo.clone(),
+            payments: payments.clone(),
+            fees: fees.clone(),
+            referrer: beneficiaries.referrer.clone(),
+            relayer: beneficiaries.relayer.clone(),
+            release_round: self.blockchain().get_block_round() + delay,
+        });
+    }
+
+    #[endpoint(reverseTransfer)]
+    fn reverse_transfer(&self, id: u64) {
+        let pending = self.take_pending_transfer(id);
+        require!(
+            self.blockchain().get_caller() == pending.from,
+            "Only the sender can reverse the transfer"
+        );
+        // a transfer to a recipient denied meanwhile cannot be finalized, so it stays reversible
+        require!(
+            self.blockchain().get_block_round() < pending.release_round
+                || self.denied_recipients().contains(&pending.to),
+            "Reversal window is over"
+        );
+
+        let mut refunds = pending.payments.clone();
+        if self.reversal_fee_refund().get() {
+            refunds.append_vec(pending.fees.clone());
+        } else {
+            self.accrue_fees(&pending.fees, &pending.beneficiaries());
+        }
+        self.transfer_reversed_event(id, &pending.from, &refunds);
+        self.tx()
+            .to(&pending.from)
+            .payment(&refunds)
+            .transfer_if_not_empty();
+    }
+
+    #[endpoint(finalizeTransfer)]
+    fn finalize_transfer(&self, id: u64) {
+        let pending = self.take_pending_transfer(id);
+        require!(
+            self.blockchain().get_block_round() >= pending.release_round,
+            "Transfer is still reversible"
+        );
+        self.require_recipient_allowed(&pending.to);
+
+        self.accrue_fees(&pending.fees, &pending.beneficiaries());
+        self.transfer_event(&pending.from, &pending.to, &pending.memo, &pending.payments);
+        self.send_payments(&pending.to, &pending.payments, &pending.memo);
+    }
+
+    // returns the payments left to forward once the fees are taken, the taken fees are added to `fees`
+    fn take_transfer_fees(
+        &self,
+        from: &ManagedAddress,
+        payments: &ManagedVec<EsdtTokenPayment>,
+        fees: &mut ManagedVec<EsdtTokenPayment>,
+        refunds: &mut ManagedVec<EsdtTokenPayment>,
+        allow_fee_credit: bool,
+    ) -> ManagedVec<EsdtTokenPayment> {
+        let fee_credit_owner = if allow_fee_credit { Some(from) } else { None };
+        let mut discount = None;
+        let mut fee_options = ManagedVec::<Self::Api, ManagedVec<EsdtTokenPayment>>::new();
+        for payment in payments.iter() {
+            let mut options = ManagedVec::<Self::Api, EsdtTokenPayment>::new();
+            for option in self
+                .get_exact_value_options(&self.token_fee(&payment.token_identifier).get(), &payment)
+                .iter()
+            {
+                let amount =
+                    apply_fee_discount(&option.amount, self.get_fee_discount(from, &mut discount));
+                options.push(EsdtTokenPayment::new(
+                    option.token_identifier,
+                    option.token_nonce,
+                    amount,
+                ));
+            }
+            fee_options.push(options);
+        }
+
+        // payments without a fixed fee, and the fee payments, are candidates for covering the fixed fees
+        let fee_payments = find_fee_payments(payments, &fee_options);
+        let mut fee_candidates = ManagedVec::<Self::Api, usize>::new();
+        let mut fixed_fee_payers = ManagedVec::<Self::Api, usize>::new();
+        let mut owed_options = ManagedVec::<Self::Api, ManagedVec<EsdtTokenPayment>>::new();
+        for (index, options) in fee_options.iter().enumerate() {
+            if options.is_empty() || fee_payments.contains(&index) {
+                fee_candidates.push(index);
+                owed_options.push(ManagedVec::new());
+            } else {
+                fixed_fee_payers.push(index);
+                owed_options.push(options.clone_value());
+            }
+        }
+        self.collect_exact_value_fees(
+            fee_credit_owner,
+            payments,
+            &owed_options,
+            &mut fee_candidates,
+            fees,
+            refunds,
+        );
+
+        // the fee payments not used for a fee are forwarded, so they owe their own fee
+        let mut leftover_options = ManagedVec::<Self::Api, ManagedVec<EsdtTokenPayment>>::new();
+        let mut has_leftover = false;
+        for (index, options) in fee_options.iter().enumerate() {
+            if fee_payments.contains(&index) && fee_candidates.contains(&index) {
+                remove_index(&mut fee_candidates, index);
+                fixed_fee_payers.push(index);
+                leftover_options.push(options.clone_value());
+                has_leftover = true;
+            } else {
+                leftover_options.push(ManagedVec::new());
+            }
+        }
+        if has_leftover {
+            self.collect_exact_value_fees(
+                fee_credit_owner,
+                payments,
+                &leftover_options,
+                &mut fee_candidates,
+                fees,
+                refunds,
+            );
+        }
+
         let mut new_payments = ManagedVec::new();
+        for (index, payment) in payments.iter().enumerate() {
+            let has_exact_value_fee = fixed_fee_payers.contains(&index);
+            if !has_exact_value_fee && !fee_candidates.contains(&index) {
+                continue;
+            }
 
-        let mut payments_iter = payments.iter();
-        while let Some(payment) = payments_iter.next() {
+            self.require_token_allowed(&payment.token_identifier);
+            self.require_transfer_limits(&payment);
+            self.record_transfer(&payment);
+            if has_exact_value_fee {
+                new_payments.push(payment);
+                continue;
+            }
             let fee_type = self.token_fee(&payment.token_identifier).get();
             match &fee_type {
-                Fee::ExactValue(fee) => {
-                    let next_payment = payments_iter
-                        .next()
-                        .unwrap_or_else(|| sc_panic!("Fee payment missing"));
-                    require!(
-                        next_payment.token_identifier == fee.token_identifier
-                            && next_payment.token_nonce == fee.token_nonce,
-                        "Wrong fee token"
-                    );
-                    require!(
-                        next_payment.amount == fee.amount,
-                        "Mismatching payment for covering fees"
-                    );
-                    let _ = self.get_payment_after_fees(fee_type, &next_payment);
+                Fee::Percentage(_) | Fee::Fraction(_) => {
+                    new_payments.push(self.get_payment_after_fees(
+                        from,
+                        fee_type,
+                        &payment,
+                        fees,
+                        &mut discount,
+                        fee_credit_owner,
+                    ));
+                }
+                _ => {
                     new_payments.push(payment);
                 }
-                Fee::Percentage(_) => {
-                    new_payments.push(self.get_payment_after_fees(fee_type, &payment));
+            }
+        }
+        new_payments
+    }
+
+    fn get_exact_value_options(
+        &self,
+        fee: &Fee<Self::Api>,
+        payment: &EsdtTokenPayment,
+    ) -> ManagedVec<EsdtTokenPayment> {
+        match fee {
+            Fee::OraclePriced(priced_fee) => {
+                ManagedVec::from_single_item(self.get_oracle_priced_fee(priced_fee))
+            }
+            Fee::Royalties(royalty_fee) => {
+                ManagedVec::from_single_item(self.get_royalties_fee(royalty_fee, payment))
+            }
+            _ => fee.exact_value_options(),
+        }
+    }
+
+    // the royalties applied to the reference amount for every transfered unit, rounded up,
+    // fungible tokens have no royalties so their fee is waived
+    fn get_royalties_fee(
+        &self,
+        fee: &RoyaltyFee<Self::Api>,
+        payment: &EsdtTokenPayment,
+    ) -> EsdtTokenPayment {
+        let royalties = if payment.token_nonce == 0 {
+            BigUint::zero()
+        } else {
+            self.blockchain()
+                .get_esdt_token_data(
+                    &self.blockchain().get_sc_address(),
+                    &payment.token_identifier,
+                    payment.token_nonce,
+                )
+                .royalties
+        };
+        let scaled_amount = &fee.reference_amount * &royalties * &payment.amount;
+        let amount = (scaled_amount + PERCENTAGE_DIVISOR - 1u32) / PERCENTAGE_DIVISOR;
+        EsdtTokenPayment::new(fee.fee_token.clone(), 0, amount)
+    }
+
+    fn collect_exact_value_fees(
+        &self,
+        fee_credit_owner: Option<&ManagedAddress>,
+        payments: &ManagedVec<EsdtTokenPayment>,
+        fee_options: &ManagedVec<ManagedVec<EsdtTokenPayment>>,
+        fee_candidates: &mut ManagedVec<usize>,
+        taken_fees: &mut ManagedVec<EsdtTokenPayment>,
+        refunds: &mut ManagedVec<EsdtTokenPayment>,
+    ) {
+        let mut fees = ManagedVec::<Self::Api, EsdtTokenPayment>::new();
+        for options in fee_options.iter() {
+            // a full loyalty discount waives the fee
+            if options.is_empty() || options.iter().any(|option| option.amount == 0) {
+                continue;
+            }
+            if let Some(fee) = self.choose_fee_option(payments, &options, fee_candidates) {
+                fees.push(fee);
+                continue;
+            }
+
+            // fees without a matching attached payment are drawn from the sender's fee credit
+            let credit_fee = options.iter().find(|option| {
+                fee_credit_owner.is_some_and(|owner| self.try_draw_fee_credit(owner, option))
+            });
+            match credit_fee {
+                Some(fee) => taken_fees.push(fee),
+                None => {
+                    require!(!fee_candidates.is_empty(), "Fee payment missing");
+                    sc_panic!("Wrong fee token");
                 }
-                Fee::Unset => {
-                    new_payments.push(payment);
+            }
+        }
+
+        let mut fee_totals = ManagedVec::<Self::Api, EsdtTokenPayment>::new();
+        for fee in fees.iter() {
+            match fee_totals
+                .iter()
+                .position(|total| is_same_token(&total, &fee))
+            {
+                Some(position) => fee_totals.get_mut(position).amount += &fee.amount,
+                // cloned, so the total does not share its amount handle with the fee
+                None => fee_totals.push(fee.clone()),
+            }
+        }
+
+        for fee_total in fee_totals.iter() {
+            let mut fee_amounts = ManagedVec::<Self::Api, BigUint>::new();
+            for fee in fees.iter() {
+                if is_same_token(&fee, &fee_total) {
+                    fee_amounts.push(fee.amount);
                 }
             }
+            self.take_fee_payment(payments, &fee_total, &fee_amounts, fee_candidates, refunds);
+            taken_fees.push(fee_total);
+        }
+    }
+
+    // prefers an option that is paid directly, then one that can be swapped into
+    fn choose_fee_option(
+        &self,
+        payments: &ManagedVec<EsdtTokenPayment>,
+        options: &ManagedVec<EsdtTokenPayment>,
+        fee_candidates: &ManagedVec<usize>,
+    ) -> Option<EsdtTokenPayment> {
+        for option in options.iter() {
+            if fee_candidates
+                .iter()
+                .any(|index| is_same_token(&payments.get(index), &option))
+            {
+                return Some(option);
+            }
         }
-        self.tx().to(&address).payment(&new_payments).transfer();
+        options.iter().find(|option| {
+            fee_candidates
+                .iter()
+                .any(|index| self.can_swap_into_fee(&payments.get(index), option))
+        })
+    }
+
+    // the fee is covered either by one aggregated payment, by one payment per fee
+    // or, if refunds are enabled, by the smallest payment exceeding the total
+    fn take_fee_payment(
+        &self,
+        payments: &ManagedVec<EsdtTokenPayment>,
+        fee_total: &EsdtTokenPayment,
+        fee_amounts: &ManagedVec<BigUint>,
+        fee_candidates: &mut ManagedVec<usize>,
+        refunds: &mut ManagedVec<EsdtTokenPayment>,
+    ) {
+        let mut matching = ManagedVec::<Self::Api, usize>::new();
+        for index in fee_candidates.iter() {
+            if is_same_token(&payments.get(index), fee_total) {
+                matching.push(index);
+            }
+        }
+
+        if matching.is_empty() {
+            let swap_index = fee_candidates
+                .iter()
+                .find(|index| self.can_swap_into_fee(&payments.get(*index), fee_total))
+                .unwrap_or_else(|| sc_panic!("Wrong fee token"));
+            remove_index(fee_candidates, swap_index);
+            let _ = self.swap_into_fee(payments.get(swap_index), fee_total, refunds);
+            return;
+        }
+
+        if let Some(index) = matching
+            .iter()
+            .find(|index| payments.get(*index).amount == fee_total.amount)
+        {
+            remove_index(fee_candidates, index);
+            return;
+        }
+
+        if let Some(indexes) = match_fee_amounts(payments, &matching, fee_amounts) {
+            for index in indexes.iter() {
+                remove_index(fee_candidates, index);
+            }
+            return;
+        }
+
+        require!(
+            self.fee_overpayment_refund().get(),
+            "Mismatching payment for covering fees"
+        );
+        let index = matching
+            .iter()
+            .filter(|index| payments.get(*index).amount > fee_total.amount)
+            .min_by(|a, b| payments.get(*a).amount.cmp(&payments.get(*b).amount))
+            .unwrap_or_else(|| sc_panic!("Mismatching payment for covering fees"));
+        remove_index(fee_candidates, index);
+        refunds.push(EsdtTokenPayment::new(
+            fee_total.token_identifier.clone(),
+            fee_total.token_nonce,
+            &payments.get(index).amount - &fee_total.amount,
+        ));
+    }
+
+    fn send_payments(
+        &self,
+        to: &ManagedAddress,
+        payments: &ManagedVec<EsdtTokenPayment<Self::Api>>,
+        memo: &ManagedBuffer,
+    ) {
+        // contracts would interpret the memo as an endpoint name, so they only get it in the event
+        if memo.is_empty() || self.blockchain().is_smart_contract(to) {
+            self.tx().to(to).payment(payments).transfer();
+        } else {
+            self.tx()
+                .to(to)
+                .payment(payments)
+                .raw_call(memo.clone())
+                .gas(0)
+                .transfer_execute();
+        }
+    }
+
+    // the sender's discount is only looked up once a fee is charged,
+    // as reading the staking discount is a synchronous call
+    fn get_fee_discount(&self, from: &ManagedAddress, discount: &mut Option<u32>) -> u32 {
+        *discount.get_or_insert_with(|| {
+            self.get_loyalty_discount(from)
+                .max(self.get_staking_discount(from))
+        })
     }
 
     fn get_payment_after_fees(
         &self,
+        from: &ManagedAddress,
         fee: Fee<Self::Api>,
         payment: &EsdtTokenPayment<Self::Api>,
+        fees: &mut ManagedVec<EsdtTokenPayment>,
+        discount: &mut Option<u32>,
+        fee_credit_owner: Option<&ManagedAddress>,
     ) -> EsdtTokenPayment<Self::Api> {
         let mut new_payment = payment.clone();
-        let fee_payment = self.calculate_fee(&fee, payment.clone());
-
-        self.paid_fees()
-            .entry((
-                new_payment.token_identifier.clone(),
-                new_payment.token_nonce,
-            ))
-            .or_insert(0u64.into())
-            .update(|value| *value += &fee_payment.amount);
+        let mut fee_payment = self.calculate_fee(from, &fee, payment.clone());
+        if fee_payment.amount == 0 {
+            return new_payment;
+        }
+        fee_payment.amount =
+            apply_fee_discount(&fee_payment.amount, self.get_fee_discount(from, discount));
+        if fee_payment.amount == 0 {
+            return new_payment;
+        }
 
-        new_payment.amount -= &fee_payment.amount;
+        // the recipient gets the whole amount when the fee is covered by the sender's fee credit
+        let drawn_from_credit =
+            fee_credit_owner.is_some_and(|owner| self.try_draw_fee_credit(owner, &fee_payment));
+        if !drawn_from_credit {
+            new_payment.amount -= &fee_payment.amount;
+        }
+        fees.push(fee_payment);
         new_payment
     }
 
+    fn accrue_fees(
+        &self,
+        fees: &ManagedVec<EsdtTokenPayment>,
+        beneficiaries: &FeeBeneficiaries<Self::Api>,
+    ) {
+        for fee in fees.iter() {
+            self.accrue_fee(
+                &fee.token_identifier,
+                fee.token_nonce,
+                &fee.amount,
+                beneficiaries,
+            );
+        }
+    }
+
+    fn accrue_fee(
+        &self,
+        token: &TokenIdentifier,
+        nonce: u64,
+        amount: &BigUint,
+        beneficiaries: &FeeBeneficiaries<Self::Api>,
+    ) {
+        self.record_fee_collected(token, nonce, amount);
+        let mut remaining_amount = amount - &self.burn_fee_share(token, nonce, amount);
+        if let Some(relayer) = &beneficiaries.relayer {
+            remaining_amount -= self.credit_relayer_share(relayer, token, nonce, &remaining_amount);
+        }
+        if let Some(referrer) = &beneficiaries.referrer {
+            remaining_amount -=
+                self.credit_referral_share(referrer, token, nonce, &remaining_amount);
+        }
+        if let Some(sender) = &beneficiaries.sender {
+            remaining_amount -=
+                self.credit_rebate_share(sender, token, nonce, amount, &remaining_amount);
+        }
+        if remaining_amount == 0 {
+            return;
+        }
+
+        // the fee tokens set is only touched when a token starts or stops having unclaimed fees
+        let paid_fee_mapper = self.paid_fee_amount(token, nonce);
+        let previous_amount = paid_fee_mapper.get();
+        let is_new_fee_token = previous_amount == 0;
+        let accrued_amount = previous_amount + remaining_amount;
+        if self.is_sweep_due(token, &accrued_amount) {
+            if !is_new_fee_token {
+                paid_fee_mapper.clear();
+                self.paid_fee_tokens().swap_remove(&(token.clone(), nonce));
+            }
+            self.sweep_fees(token, nonce, accrued_amount);
+            return;
+        }
+
+        if is_new_fee_token {
+            self.paid_fee_tokens().insert((token.clone(), nonce));
+        }
+        paid_fee_mapper.set(accrued_amount);
+    }
+
+    // the part of the payment taken by a proportional fee
     fn calculate_fee(
         &self,
+        sender: &ManagedAddress,
         fee: &Fee<Self::Api>,
         mut provided: EsdtTokenPayment<Self::Api>,
     ) -> EsdtTokenPayment<Self::Api> {
         match fee {
-            Fee::ExactValue(requested) => requested.clone(),
             Fee::Percentage(percentage) => {
-                let calculated_fee_amount = &provided.amount * *percentage / PERCENTAGE_DIVISOR;
-                provided.amount = calculated_fee_amount;
+                provided.amount = self.calculate_fraction_fee(
+                    sender,
+                    &provided.token_identifier,
+                    &provided.amount,
+                    u64::from(*percentage),
+                    u64::from(PERCENTAGE_DIVISOR),
+                );
                 provided
             }
-            Fee::Unset => {
+            Fee::Fraction(fraction) => {
+                provided.amount = self.calculate_fraction_fee(
+                    sender,
+                    &provided.token_identifier,
+                    &provided.amount,
+                    fraction.numerator,
+                    fraction.denominator,
+                );
+                provided
+            }
+            // the fixed fees are paid with separate payments, see `collect_exact_value_fees`
+            _ => {
                 provided.amount = BigUint::zero();
                 provided
             }
         }
     }
 
+    #[event("transfer")]
+    fn transfer_event(
+        &self,
+        #[indexed] from: &ManagedAddress,
+        #[indexed] to: &ManagedAddress,
+        #[indexed] memo: &ManagedBuffer,
+        payments: &ManagedVec<EsdtTokenPayment<Self::Api>>,
+    );
+
     #[view(getTokenFee)]
     #[storage_mapper("token_fee")]
     fn token_fee(&self, token: &TokenIdentifier) -> SingleValueMapper<Fee<Self::Api>>;
 
+    #[view(isFeeOverpaymentRefundEnabled)]
+    #[storage_mapper("fee_overpayment_refund")]
+    fn fee_overpayment_refund(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("token_fee")]
+    fn legacy_token_fee(&self, token: &TokenIdentifier) -> SingleValueMapper<FeeV1<Self::Api>>;
+
+    #[view(getFeeTokens)]
+    #[storage_mapper("fee_tokens")]
+    fn fee_tokens(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    #[view(getStorageVersion)]
+    #[storage_mapper("storage_version")]
+    fn storage_version(&self) -> SingleValueMapper<u32>;
+
     #[view(getPaidFees)]
+    fn paid_fees(&self) -> MultiValueEncoded<MultiValue2<(TokenIdentifier, u64), BigUint>> {
+        let mut paid_fees = MultiValueEncoded::new();
+        for (token, nonce) in self.paid_fee_tokens().iter() {
+            let amount = self.paid_fee_amount(&token, nonce).get();
+            paid_fees.push(((token, nonce), amount).into());
+        }
+        paid_fees
+    }
+
     #[storage_mapper("paid_fees")]
-    fn paid_fees(&self) -> MapMapper<(TokenIdentifier, u64), BigUint>;
+    fn legacy_paid_fees(&self) -> MapMapper<(TokenIdentifier, u64), BigUint>;
+
+    #[view(getPaidFeeTokens)]
+    #[storage_mapper("paid_fee_tokens")]
+    fn paid_fee_tokens(&self) -> UnorderedSetMapper<(TokenIdentifier, u64)>;
+
+    #[view(getPaidFeeAmount)]
+    #[storage_mapper("paid_fee_amount")]
+    fn paid_fee_amount(&self, token: &TokenIdentifier, nonce: u64) -> SingleValueMapper<BigUint>;
 }
@@ -0,0 +1,1395 @@
+// Code generated by the multiversx-sc proxy generator. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+#![allow(dead_code)]
+#![allow(clippy::all)]
+
+use multiversx_sc::proxy_imports::*;
+
+pub struct EsdtTransferWithFeeProxy;
+
+impl<Env, From, To, Gas> TxProxyTrait<Env, From, To, Gas> for EsdtTransferWithFeeProxy
+where
+    Env: TxEnv,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    type TxProxyMethods = EsdtTransferWithFeeProxyMethods<Env, From, To, Gas>;
+
+    fn proxy_methods(self, tx: Tx<Env, From, To, (), Gas, (), ()>) -> Self::TxProxyMethods {
+        EsdtTransferWithFeeProxyMethods { wrapped_tx: tx }
+    }
+}
+
+pub struct EsdtTransferWithFeeProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    wrapped_tx: Tx<Env, From, To, (), Gas, (), ()>,
+}
+
+#[rustfmt::skip]
+impl<Env, From, Gas> EsdtTransferWithFeeProxyMethods<Env, From, (), Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn init(
+        self,
+    ) -> TxTypedDeploy<Env, From, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_deploy()
+            .original_result()
+    }
+}
+
+#[rustfmt::skip]
+impl<Env, From, To, Gas> EsdtTransferWithFeeProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn upgrade<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, TokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        legacy_fee_tokens: Arg0,
+    ) -> TxTypedUpgrade<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_upgrade()
+            .argument(&legacy_fee_tokens)
+            .original_result()
+    }
+}
+
+#[rustfmt::skip]
+impl<Env, From, To, Gas> EsdtTransferWithFeeProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn set_exact_value_fee<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        fee_token: Arg0,
+        fee_amount: Arg1,
+        token: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setExactValueFee")
+            .argument(&fee_token)
+            .argument(&fee_amount)
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn set_exact_value_fee_options<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<MultiValueEncoded<Env::Api, MultiValue2<TokenIdentifier<Env::Api>, BigUint<Env::Api>>>>,
+    >(
+        self,
+        token: Arg0,
+        fee_options: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setExactValueFeeOptions")
+            .argument(&token)
+            .argument(&fee_options)
+            .original_result()
+    }
+
+    pub fn set_oracle_priced_fee<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<u32>,
+        Arg2: ProxyArg<BigUint<Env::Api>>,
+        Arg3: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        fee_token: Arg0,
+        fee_token_decimals: Arg1,
+        reference_amount: Arg2,
+        token: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setOraclePricedFee")
+            .argument(&fee_token)
+            .argument(&fee_token_decimals)
+            .argument(&reference_amount)
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn set_percentage_fee<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        fee: Arg0,
+        token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setPercentageFee")
+            .argument(&fee)
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn set_fraction_fee<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        numerator: Arg0,
+        denominator: Arg1,
+        token: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setFractionFee")
+            .argument(&numerator)
+            .argument(&denominator)
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn set_royalties_fee<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        fee_token: Arg0,
+        reference_amount: Arg1,
+        token: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setRoyaltiesFee")
+            .argument(&fee_token)
+            .argument(&reference_amount)
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn set_fee_overpayment_refund<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        enabled: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setFeeOverpaymentRefund")
+            .argument(&enabled)
+            .original_result()
+    }
+
+    pub fn claim_fees(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("claimFees")
+            .original_result()
+    }
+
+    pub fn transfer<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<OptionalValue<ManagedBuffer<Env::Api>>>,
+        Arg2: ProxyArg<OptionalValue<ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        address: Arg0,
+        opt_memo: Arg1,
+        opt_referrer: Arg2,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("transfer")
+            .argument(&address)
+            .argument(&opt_memo)
+            .argument(&opt_referrer)
+            .original_result()
+    }
+
+    pub fn split_transfer<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, MultiValue2<ManagedAddress<Env::Api>, u32>>>,
+    >(
+        self,
+        recipients: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("splitTransfer")
+            .argument(&recipients)
+            .original_result()
+    }
+
+    pub fn transfer_with_permit<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<Permit<Env::Api>>,
+        Arg2: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        signer: Arg0,
+        permit: Arg1,
+        signature: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("transferWithPermit")
+            .argument(&signer)
+            .argument(&permit)
+            .argument(&signature)
+            .original_result()
+    }
+
+    pub fn reverse_transfer<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("reverseTransfer")
+            .argument(&id)
+            .original_result()
+    }
+
+    pub fn finalize_transfer<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("finalizeTransfer")
+            .argument(&id)
+            .original_result()
+    }
+
+    pub fn token_fee<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Fee<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTokenFee")
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn fee_overpayment_refund(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isFeeOverpaymentRefundEnabled")
+            .original_result()
+    }
+
+    pub fn fee_tokens(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, TokenIdentifier<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFeeTokens")
+            .original_result()
+    }
+
+    pub fn storage_version(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getStorageVersion")
+            .original_result()
+    }
+
+    pub fn paid_fees(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<(TokenIdentifier<Env::Api>, u64), BigUint<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPaidFees")
+            .original_result()
+    }
+
+    pub fn paid_fee_tokens(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, (TokenIdentifier<Env::Api>, u64)>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPaidFeeTokens")
+            .original_result()
+    }
+
+    pub fn paid_fee_amount<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        token: Arg0,
+        nonce: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPaidFeeAmount")
+            .argument(&token)
+            .argument(&nonce)
+            .original_result()
+    }
+
+    pub fn set_fee_burn_share<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        share: Arg0,
+        token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setFeeBurnShare")
+            .argument(&share)
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn fee_burn_share<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFeeBurnShare")
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn burned_fees<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        token: Arg0,
+        nonce: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getBurnedFees")
+            .argument(&token)
+            .argument(&nonce)
+            .original_result()
+    }
+
+    pub fn set_fee_credit<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        enabled: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setFeeCredit")
+            .argument(&enabled)
+            .original_result()
+    }
+
+    pub fn fee_credit_enabled<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        owner: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isFeeCreditEnabled")
+            .argument(&owner)
+            .original_result()
+    }
+
+    pub fn set_price_aggregator<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg2: ProxyArg<u8>,
+        Arg3: ProxyArg<u64>,
+    >(
+        self,
+        address: Arg0,
+        reference_currency: Arg1,
+        price_decimals: Arg2,
+        max_price_age: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setPriceAggregator")
+            .argument(&address)
+            .argument(&reference_currency)
+            .argument(&price_decimals)
+            .argument(&max_price_age)
+            .original_result()
+    }
+
+    pub fn set_fee_token_price_bounds<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        fee_token: Arg0,
+        min_price: Arg1,
+        max_price: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setFeeTokenPriceBounds")
+            .argument(&fee_token)
+            .argument(&min_price)
+            .argument(&max_price)
+            .original_result()
+    }
+
+    pub fn price_aggregator(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPriceAggregator")
+            .original_result()
+    }
+
+    pub fn reference_currency(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedBuffer<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getReferenceCurrency")
+            .original_result()
+    }
+
+    pub fn price_decimals(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u8> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPriceDecimals")
+            .original_result()
+    }
+
+    pub fn max_price_age(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMaxPriceAge")
+            .original_result()
+    }
+
+    pub fn fee_token_price_bounds<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        fee_token: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, (BigUint<Env::Api>, BigUint<Env::Api>)> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFeeTokenPriceBounds")
+            .argument(&fee_token)
+            .original_result()
+    }
+
+    pub fn set_fee_rounding_mode<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<RoundingMode>,
+    >(
+        self,
+        token: Arg0,
+        mode: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setFeeRoundingMode")
+            .argument(&token)
+            .argument(&mode)
+            .original_result()
+    }
+
+    pub fn get_fee_remainder<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        sender: Arg0,
+        token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigInt<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFeeRemainder")
+            .argument(&sender)
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn fee_rounding_mode<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, RoundingMode> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFeeRoundingMode")
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn get_fee_stats<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        token: Arg0,
+        nonce: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, FeeStats<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFeeStats")
+            .argument(&token)
+            .argument(&nonce)
+            .original_result()
+    }
+
+    pub fn set_fee_swap_pair<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg2: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        input_token: Arg0,
+        fee_token: Arg1,
+        pair_address: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setFeeSwapPair")
+            .argument(&input_token)
+            .argument(&fee_token)
+            .argument(&pair_address)
+            .original_result()
+    }
+
+    pub fn remove_fee_swap_pair<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        input_token: Arg0,
+        fee_token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeFeeSwapPair")
+            .argument(&input_token)
+            .argument(&fee_token)
+            .original_result()
+    }
+
+    pub fn fee_swap_pair<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        input_token: Arg0,
+        fee_token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFeeSwapPair")
+            .argument(&input_token)
+            .argument(&fee_token)
+            .original_result()
+    }
+
+    pub fn set_treasury<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setTreasury")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn set_sweep_threshold<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        token: Arg0,
+        threshold: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setSweepThreshold")
+            .argument(&token)
+            .argument(&threshold)
+            .original_result()
+    }
+
+    pub fn treasury(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTreasury")
+            .original_result()
+    }
+
+    pub fn sweep_threshold<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSweepThreshold")
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn set_loyalty_discount<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        collection: Arg0,
+        nonce: Arg1,
+        discount: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setLoyaltyDiscount")
+            .argument(&collection)
+            .argument(&nonce)
+            .argument(&discount)
+            .original_result()
+    }
+
+    pub fn register_loyalty_tokens(
+        self,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("registerLoyaltyTokens")
+            .original_result()
+    }
+
+    pub fn withdraw_loyalty_tokens(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("withdrawLoyaltyTokens")
+            .original_result()
+    }
+
+    pub fn loyalty_discount<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        collection: Arg0,
+        nonce: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLoyaltyDiscount")
+            .argument(&collection)
+            .argument(&nonce)
+            .original_result()
+    }
+
+    pub fn loyalty_tokens<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        owner: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<(TokenIdentifier<Env::Api>, u64), BigUint<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLoyaltyTokens")
+            .argument(&owner)
+            .original_result()
+    }
+
+    pub fn deposit(
+        self,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("deposit")
+            .original_result()
+    }
+
+    pub fn withdraw_deposits(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("withdrawDeposits")
+            .original_result()
+    }
+
+    pub fn set_relayer_share<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        share: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setRelayerShare")
+            .argument(&share)
+            .original_result()
+    }
+
+    pub fn deposits<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        owner: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<(TokenIdentifier<Env::Api>, u64), BigUint<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getDeposits")
+            .argument(&owner)
+            .original_result()
+    }
+
+    pub fn permit_nonce<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        signer: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPermitNonce")
+            .argument(&signer)
+            .original_result()
+    }
+
+    pub fn relayer_share(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRelayerShare")
+            .original_result()
+    }
+
+    pub fn add_denied_recipients<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        recipients: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addDeniedRecipients")
+            .argument(&recipients)
+            .original_result()
+    }
+
+    pub fn remove_denied_recipients<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        recipients: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeDeniedRecipients")
+            .argument(&recipients)
+            .original_result()
+    }
+
+    pub fn set_payable_recipient_required<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        required: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setPayableRecipientRequired")
+            .argument(&required)
+            .original_result()
+    }
+
+    pub fn denied_recipients(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getDeniedRecipients")
+            .original_result()
+    }
+
+    pub fn payable_recipient_required(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isPayableRecipientRequired")
+            .original_result()
+    }
+
+    pub fn set_rebate_share<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        share: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setRebateShare")
+            .argument(&share)
+            .original_result()
+    }
+
+    pub fn claim_rebates(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("claimRebates")
+            .original_result()
+    }
+
+    pub fn get_claimable_rebates<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, EsdtTokenPayment<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getClaimableRebates")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn rebate_share(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRebateShare")
+            .original_result()
+    }
+
+    pub fn rebate_pool<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u64>,
+    >(
+        self,
+        token: Arg0,
+        nonce: Arg1,
+        epoch: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRebatePool")
+            .argument(&token)
+            .argument(&nonce)
+            .argument(&epoch)
+            .original_result()
+    }
+
+    pub fn rebate_pool_weight<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u64>,
+    >(
+        self,
+        token: Arg0,
+        nonce: Arg1,
+        epoch: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRebatePoolWeight")
+            .argument(&token)
+            .argument(&nonce)
+            .argument(&epoch)
+            .original_result()
+    }
+
+    pub fn rebate_weights<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        sender: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<(TokenIdentifier<Env::Api>, u64), (u64, BigUint<Env::Api>)>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRebateWeights")
+            .argument(&sender)
+            .original_result()
+    }
+
+    pub fn rebates<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        sender: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<(TokenIdentifier<Env::Api>, u64), BigUint<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRebates")
+            .argument(&sender)
+            .original_result()
+    }
+
+    pub fn add_referrers<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        referrers: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addReferrers")
+            .argument(&referrers)
+            .original_result()
+    }
+
+    pub fn remove_referrers<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        referrers: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeReferrers")
+            .argument(&referrers)
+            .original_result()
+    }
+
+    pub fn set_referral_share<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        share: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setReferralShare")
+            .argument(&share)
+            .original_result()
+    }
+
+    pub fn claim_referral_fees(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("claimReferralFees")
+            .original_result()
+    }
+
+    pub fn referrers(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getReferrers")
+            .original_result()
+    }
+
+    pub fn referral_share(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getReferralShare")
+            .original_result()
+    }
+
+    pub fn referral_fees<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        referrer: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<(TokenIdentifier<Env::Api>, u64), BigUint<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getReferralFees")
+            .argument(&referrer)
+            .original_result()
+    }
+
+    pub fn set_settlement_delay<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        token: Arg0,
+        rounds: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setSettlementDelay")
+            .argument(&token)
+            .argument(&rounds)
+            .original_result()
+    }
+
+    pub fn set_reversal_fee_refund<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        enabled: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setReversalFeeRefund")
+            .argument(&enabled)
+            .original_result()
+    }
+
+    pub fn settlement_delay<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSettlementDelay")
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn reversal_fee_refund(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isReversalFeeRefundEnabled")
+            .original_result()
+    }
+
+    pub fn pending_transfers<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, PendingTransfer<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPendingTransfer")
+            .argument(&id)
+            .original_result()
+    }
+
+    pub fn set_staking_contract<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+        view_name: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setStakingContract")
+            .argument(&address)
+            .argument(&view_name)
+            .original_result()
+    }
+
+    pub fn remove_staking_contract(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeStakingContract")
+            .original_result()
+    }
+
+    pub fn set_staking_discount_tiers<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, MultiValue2<BigUint<Env::Api>, u32>>>,
+    >(
+        self,
+        tiers: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setStakingDiscountTiers")
+            .argument(&tiers)
+            .original_result()
+    }
+
+    pub fn staking_contract(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getStakingContract")
+            .original_result()
+    }
+
+    pub fn staking_view(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedBuffer<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getStakingView")
+            .original_result()
+    }
+
+    pub fn staking_discount_tiers(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedVec<Env::Api, StakingTier<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getStakingDiscountTiers")
+            .original_result()
+    }
+
+    pub fn set_token_policy<
+        Arg0: ProxyArg<TokenPolicy>,
+    >(
+        self,
+        policy: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setTokenPolicy")
+            .argument(&policy)
+            .original_result()
+    }
+
+    pub fn add_policy_tokens<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, TokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        tokens: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addPolicyTokens")
+            .argument(&tokens)
+            .original_result()
+    }
+
+    pub fn remove_policy_tokens<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, TokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        tokens: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removePolicyTokens")
+            .argument(&tokens)
+            .original_result()
+    }
+
+    pub fn set_transfer_limits<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        token: Arg0,
+        min_amount: Arg1,
+        max_amount: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setTransferLimits")
+            .argument(&token)
+            .argument(&min_amount)
+            .argument(&max_amount)
+            .original_result()
+    }
+
+    pub fn token_policy(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, TokenPolicy> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTokenPolicy")
+            .original_result()
+    }
+
+    pub fn policy_tokens(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, TokenIdentifier<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPolicyTokens")
+            .original_result()
+    }
+
+    pub fn transfer_limits<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, (BigUint<Env::Api>, BigUint<Env::Api>)> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTransferLimits")
+            .argument(&token)
+            .original_result()
+    }
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct Permit<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub recipient: ManagedAddress<Api>,
+    pub payment: EsdtTokenPayment<Api>,
+    pub fee: EsdtTokenPayment<Api>,
+    pub nonce: u64,
+    pub deadline: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Eq, Clone)]
+pub enum Fee<Api>
+where
+    Api: ManagedTypeApi,
+{
+    Unset,
+    ExactValue(EsdtTokenPayment<Api>),
+    Percentage(u32),
+    ExactValueOptions(ManagedVec<Api, EsdtTokenPayment<Api>>),
+    OraclePriced(PricedFee<Api>),
+    Fraction(FeeFraction),
+    Royalties(RoyaltyFee<Api>),
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Eq, Clone)]
+pub struct PricedFee<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub fee_token: TokenIdentifier<Api>,
+    pub fee_token_decimals: u32,
+    pub reference_amount: BigUint<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Eq, Clone, Copy)]
+pub struct FeeFraction {
+    pub numerator: u64,
+    pub denominator: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Eq, Clone)]
+pub struct RoyaltyFee<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub fee_token: TokenIdentifier<Api>,
+    pub reference_amount: BigUint<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Eq, Clone, Copy)]
+pub enum RoundingMode {
+    Down,
+    Up,
+    HalfEven,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct FeeStats<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub fees_collected: BigUint<Api>,
+    pub fees_claimed: BigUint<Api>,
+    pub volume: BigUint<Api>,
+    pub transfer_count: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct PendingTransfer<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub from: ManagedAddress<Api>,
+    pub to: ManagedAddress<Api>,
+    pub memo: ManagedBuffer<Api>,
+    pub payments: ManagedVec<Api, EsdtTokenPayment<Api>>,
+    pub fees: ManagedVec<Api, EsdtTokenPayment<Api>>,
+    pub referrer: Option<ManagedAddress<Api>>,
+    pub relayer: Option<ManagedAddress<Api>>,
+    pub release_round: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem)]
+pub struct StakingTier<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub min_stake: BigUint<Api>,
+    pub discount: u32,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Eq, Clone, Copy)]
+pub enum TokenPolicy {
+    Open,
+    AllowlistOnly,
+    Denylist,
+}
@@ -3,6 +3,8 @@ use multiversx_sc::imports::*;
 
 pub(crate) const PERCENTAGE_DIVISOR: u32 = 10_000; // dividing the percentage fee by this number will result in a 2 decimal percentage
 
+// new variants must only be appended, any other change to the encoding
+// needs a new storage version and a migration step in `upgrade`
 #[type_abi]
 #[derive(TopEncode, TopDecode, PartialEq, Eq, Clone)]
 pub enum Fee<M>
@@ -12,4 +14,97 @@ where
     Unset,
     ExactValue(EsdtTokenPayment<M>),
     Percentage(u32),
+    ExactValueOptions(ManagedVec<M, EsdtTokenPayment<M>>),
+    OraclePriced(PricedFee<M>),
+    Fraction(FeeFraction),
+    Royalties(RoyaltyFee<M>),
+}
+
+// a percentage fee with a custom precision, e.g. 50 / 1_000_000 for 0.005%
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Eq, Clone, Copy)]
+pub struct FeeFraction {
+    pub numerator: u64,
+    pub denominator: u64,
+}
+
+// a fixed fee expressed in the reference currency of the price aggregator
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Eq, Clone)]
+pub struct PricedFee<M>
+where
+    M: ManagedTypeApi,
+{
+    pub fee_token: TokenIdentifier<M>,
+    pub fee_token_decimals: u32,
+    pub reference_amount: BigUint<M>,
+}
+
+// a fixed fee for NFTs and SFTs, scaled by their royalties
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Eq, Clone)]
+pub struct RoyaltyFee<M>
+where
+    M: ManagedTypeApi,
+{
+    pub fee_token: TokenIdentifier<M>,
+    pub reference_amount: BigUint<M>,
+}
+
+impl<M> Fee<M>
+where
+    M: ManagedTypeApi,
+{
+    // the fee payments accepted for a token, any one of them covers the fee,
+    // oracle priced and royalties fees are only known at transfer time
+    pub fn exact_value_options(&self) -> ManagedVec<M, EsdtTokenPayment<M>> {
+        match self {
+            Fee::ExactValue(fee) => ManagedVec::from_single_item(fee.clone()),
+            Fee::ExactValueOptions(options) => options.clone(),
+            Fee::Unset
+            | Fee::Percentage(_)
+            | Fee::OraclePriced(_)
+            | Fee::Fraction(_)
+            | Fee::Royalties(_) => ManagedVec::new(),
+        }
+    }
+}
+
+// the `Fee` layout of storage version 1, kept for migrations
+#[derive(TopEncode, TopDecode)]
+pub enum FeeV1<M>
+where
+    M: ManagedTypeApi,
+{
+    Unset,
+    ExactValue(EsdtTokenPayment<M>),
+    Percentage(u32),
+}
+
+impl<M> From<FeeV1<M>> for Fee<M>
+where
+    M: ManagedTypeApi,
+{
+    fn from(fee: FeeV1<M>) -> Self {
+        match fee {
+            FeeV1::Unset => Fee::Unset,
+            FeeV1::ExactValue(fee) => Fee::ExactValue(fee),
+            FeeV1::Percentage(percentage) => Fee::Percentage(percentage),
+        }
+    }
+}
+
+// the loyalty and staking discounts use the same 2 decimal percentage
+pub fn apply_fee_discount<M: ManagedTypeApi>(amount: &BigUint<M>, discount: u32) -> BigUint<M> {
+    amount * (PERCENTAGE_DIVISOR - discount) / PERCENTAGE_DIVISOR
+}
+
+// the addresses getting a share of the fees collected by a transfer
+pub struct FeeBeneficiaries<M>
+where
+    M: ManagedTypeApi,
+{
+    pub sender: Option<ManagedAddress<M>>,
+    pub referrer: Option<ManagedAddress<M>>,
+    pub relayer: Option<ManagedAddress<M>>,
 }
@@ -0,0 +1,55 @@
+use multiversx_sc::imports::*;
+
+use crate::fee::PERCENTAGE_DIVISOR;
+
+#[multiversx_sc::module]
+pub trait FeeBurnModule {
+    #[only_owner]
+    #[endpoint(setFeeBurnShare)]
+    fn set_fee_burn_share(&self, share: u32, token: TokenIdentifier) {
+        require!(share <= PERCENTAGE_DIVISOR, "Invalid burn share");
+        if share > 0 {
+            let roles = self.blockchain().get_esdt_local_roles(&token);
+            require!(
+                roles.has_role(&EsdtLocalRole::Burn) || roles.has_role(&EsdtLocalRole::NftBurn),
+                "Contract lacks the burn role for this token"
+            );
+        }
+        self.fee_burn_share(&token).set(share);
+    }
+
+    // returns the burned part of the fee, fees with a nonce need the NFT burn role and the others
+    // the local burn role, the share the contract cannot burn stays with the collected fee
+    fn burn_fee_share(&self, token: &TokenIdentifier, nonce: u64, fee_amount: &BigUint) -> BigUint {
+        let share = self.fee_burn_share(token).get();
+        let burn_amount = fee_amount * share / PERCENTAGE_DIVISOR;
+        if burn_amount == 0 {
+            return burn_amount;
+        }
+        let burn_role = if nonce == 0 {
+            EsdtLocalRole::Burn
+        } else {
+            EsdtLocalRole::NftBurn
+        };
+        if !self
+            .blockchain()
+            .get_esdt_local_roles(token)
+            .has_role(&burn_role)
+        {
+            return BigUint::zero();
+        }
+
+        self.send().esdt_local_burn(token, nonce, &burn_amount);
+        self.burned_fees(token, nonce)
+            .update(|value| *value += &burn_amount);
+        burn_amount
+    }
+
+    #[view(getFeeBurnShare)]
+    #[storage_mapper("fee_burn_share")]
+    fn fee_burn_share(&self, token: &TokenIdentifier) -> SingleValueMapper<u32>;
+
+    #[view(getBurnedFees)]
+    #[storage_mapper("burned_fees")]
+    fn burned_fees(&self, token: &TokenIdentifier, nonce: u64) -> SingleValueMapper<BigUint>;
+}
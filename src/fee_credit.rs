@@ -0,0 +1,20 @@
+use multiversx_sc::imports::*;
+
+// the fee credit is the sender's deposit balance, shared with the permits,
+// fees are only drawn from the deposits of the senders opting in
+#[multiversx_sc::module]
+pub trait FeeCreditModule: crate::permit::PermitModule + crate::fee_stats::FeeStatsModule {
+    #[endpoint(setFeeCredit)]
+    fn set_fee_credit(&self, enabled: bool) {
+        let caller = self.blockchain().get_caller();
+        self.fee_credit_enabled(&caller).set(enabled);
+    }
+
+    fn try_draw_fee_credit(&self, owner: &ManagedAddress, fee: &EsdtTokenPayment) -> bool {
+        self.fee_credit_enabled(owner).get() && self.try_debit_deposit(owner, fee)
+    }
+
+    #[view(isFeeCreditEnabled)]
+    #[storage_mapper("fee_credit_enabled")]
+    fn fee_credit_enabled(&self, owner: &ManagedAddress) -> SingleValueMapper<bool>;
+}
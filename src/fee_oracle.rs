@@ -0,0 +1,110 @@
+use multiversx_sc::imports::*;
+
+use crate::fee::PricedFee;
+
+const LATEST_PRICE_FEED_ENDPOINT: &str = "latestPriceFeed";
+
+#[multiversx_sc::module]
+pub trait FeeOracleModule {
+    // prices older than `max_price_age` seconds, or with other decimals than `price_decimals`, are refused
+    #[only_owner]
+    #[endpoint(setPriceAggregator)]
+    fn set_price_aggregator(
+        &self,
+        address: ManagedAddress,
+        reference_currency: ManagedBuffer,
+        price_decimals: u8,
+        max_price_age: u64,
+    ) {
+        require!(
+            self.blockchain().is_smart_contract(&address),
+            "Invalid price aggregator"
+        );
+        self.price_aggregator().set(address);
+        self.reference_currency().set(reference_currency);
+        self.price_decimals().set(price_decimals);
+        self.max_price_age().set(max_price_age);
+    }
+
+    #[only_owner]
+    #[endpoint(setFeeTokenPriceBounds)]
+    fn set_fee_token_price_bounds(
+        &self,
+        fee_token: TokenIdentifier,
+        min_price: BigUint,
+        max_price: BigUint,
+    ) {
+        require!(min_price <= max_price, "Invalid price bounds");
+        self.fee_token_price_bounds(&fee_token)
+            .set((min_price, max_price));
+    }
+
+    // the reference amount and the price have the decimals of the price feed,
+    // the fee is rounded up to the smallest unit of the fee token
+    fn get_oracle_priced_fee(&self, fee: &PricedFee<Self::Api>) -> EsdtTokenPayment {
+        let price = self.get_fee_token_price(&fee.fee_token);
+        let fee_token_unit = BigUint::from(10u64).pow(fee.fee_token_decimals);
+        let amount = (&fee.reference_amount * &fee_token_unit + &price - 1u64) / &price;
+        EsdtTokenPayment::new(fee.fee_token.clone(), 0, amount)
+    }
+
+    fn get_fee_token_price(&self, fee_token: &TokenIdentifier) -> BigUint {
+        require!(
+            !self.price_aggregator().is_empty(),
+            "Price aggregator not set"
+        );
+        let (_, _, _, timestamp, price, decimals) = self
+            .tx()
+            .to(self.price_aggregator().get())
+            .raw_call(LATEST_PRICE_FEED_ENDPOINT)
+            .argument(&fee_token.ticker())
+            .argument(&self.reference_currency().get())
+            .original_result::<MultiValue6<u32, ManagedBuffer, ManagedBuffer, u64, BigUint, u8>>()
+            .returns(ReturnsResult)
+            .sync_call()
+            .into_tuple();
+
+        require!(
+            timestamp + self.max_price_age().get() >= self.blockchain().get_block_timestamp(),
+            "Stale price"
+        );
+        require!(price > 0, "Invalid price");
+        // the reference amounts are expressed with the decimals the price feed was set up with
+        require!(
+            decimals == self.price_decimals().get(),
+            "Unexpected price decimals"
+        );
+        let bounds_mapper = self.fee_token_price_bounds(fee_token);
+        if !bounds_mapper.is_empty() {
+            let (min_price, max_price) = bounds_mapper.get();
+            require!(
+                price >= min_price && price <= max_price,
+                "Price out of bounds"
+            );
+        }
+        price
+    }
+
+    #[view(getPriceAggregator)]
+    #[storage_mapper("price_aggregator")]
+    fn price_aggregator(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getReferenceCurrency)]
+    #[storage_mapper("reference_currency")]
+    fn reference_currency(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getPriceDecimals)]
+    #[storage_mapper("price_decimals")]
+    fn price_decimals(&self) -> SingleValueMapper<u8>;
+
+    #[view(getMaxPriceAge)]
+    #[storage_mapper("max_price_age")]
+    fn max_price_age(&self) -> SingleValueMapper<u64>;
+
+    #[view(getFeeTokenPriceBounds)]
+    #[storage_mapper("fee_token_price_bounds")]
+    fn fee_token_price_bounds(
+        &self,
+        fee_token: &TokenIdentifier,
+    ) -> SingleValueMapper<(BigUint, BigUint)>;
+}
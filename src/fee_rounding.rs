@@ -0,0 +1,111 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Eq, Clone, Copy)]
+pub enum RoundingMode {
+    Down,
+    Up,
+    HalfEven,
+}
+
+#[multiversx_sc::module]
+pub trait FeeRoundingModule {
+    #[only_owner]
+    #[endpoint(setFeeRoundingMode)]
+    fn set_fee_rounding_mode(&self, token: TokenIdentifier, mode: RoundingMode) {
+        self.fee_rounding_mode(&token).set(mode);
+    }
+
+    // the difference between the exact fee and the charged fee is carried over to the sender's
+    // next transfer of the token, so splitting a transfer into small ones does not avoid the fee,
+    // and rounding up does not overcharge in the long run
+    // the remainder is kept in units of 1 / denominator, it is negative after rounding up
+    fn calculate_fraction_fee(
+        &self,
+        sender: &ManagedAddress,
+        token: &TokenIdentifier,
+        amount: &BigUint,
+        numerator: u64,
+        denominator: u64,
+    ) -> BigUint {
+        let exact_fee = BigInt::from(amount * numerator)
+            + self.get_fee_remainder(sender.clone(), token.clone());
+        if exact_fee <= 0 {
+            // an earlier fee rounded up already covered this one
+            self.store_fee_remainder(sender, token, exact_fee);
+            return BigUint::zero();
+        }
+
+        let divisor = BigUint::from(denominator);
+        let fee_amount = exact_fee.magnitude() / &divisor;
+        let remainder = exact_fee.magnitude() % &divisor;
+        let round_up = remainder > 0
+            && match self.fee_rounding_mode(token).get() {
+                RoundingMode::Down => false,
+                RoundingMode::Up => true,
+                RoundingMode::HalfEven => {
+                    let doubled_remainder = &remainder * 2u32;
+                    doubled_remainder > divisor
+                        || (doubled_remainder == divisor && &fee_amount % 2u32 == 1u32)
+                }
+            };
+        let mut charged_fee = if round_up {
+            fee_amount + 1u32
+        } else {
+            fee_amount
+        };
+        // the recipient always gets a part of the payment, the uncharged fee is carried over
+        let max_fee = amount - 1u32;
+        if charged_fee > max_fee {
+            charged_fee = max_fee;
+        }
+
+        let new_remainder = exact_fee - BigInt::from(&charged_fee * &divisor);
+        self.store_fee_remainder(sender, token, new_remainder);
+        charged_fee
+    }
+
+    fn store_fee_remainder(
+        &self,
+        sender: &ManagedAddress,
+        token: &TokenIdentifier,
+        remainder: BigInt,
+    ) {
+        let remainder_mapper = self.fee_remainder(sender, token);
+        if remainder == 0 {
+            remainder_mapper.clear();
+        } else {
+            remainder_mapper.set((self.fee_version(token).get(), remainder));
+        }
+    }
+
+    // the remainders carried before the last fee change of the token are dropped,
+    // as they are expressed in units of the previous fee
+    #[view(getFeeRemainder)]
+    fn get_fee_remainder(&self, sender: ManagedAddress, token: TokenIdentifier) -> BigInt {
+        let remainder_mapper = self.fee_remainder(&sender, &token);
+        if remainder_mapper.is_empty() {
+            return BigInt::zero();
+        }
+        let (fee_version, remainder) = remainder_mapper.get();
+        if fee_version != self.fee_version(&token).get() {
+            return BigInt::zero();
+        }
+        remainder
+    }
+
+    #[view(getFeeRoundingMode)]
+    #[storage_mapper("fee_rounding_mode")]
+    fn fee_rounding_mode(&self, token: &TokenIdentifier) -> SingleValueMapper<RoundingMode>;
+
+    #[storage_mapper("fee_remainder")]
+    fn fee_remainder(
+        &self,
+        sender: &ManagedAddress,
+        token: &TokenIdentifier,
+    ) -> SingleValueMapper<(u64, BigInt)>;
+
+    #[storage_mapper("fee_version")]
+    fn fee_version(&self, token: &TokenIdentifier) -> SingleValueMapper<u64>;
+}
@@ -0,0 +1,75 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+// lifetime counters, never reset by claims; `fees_collected` is every fee taken in the token,
+// burned and shared parts included, `fees_claimed` is what left the contract's fees through owner,
+// referral and rebate claims, sweeps to the treasury and relayer shares credited to deposits, so
+// collected - claimed - burned (see `getBurnedFees`) is still held as unclaimed fees
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct FeeStats<M: ManagedTypeApi> {
+    pub fees_collected: BigUint<M>,
+    pub fees_claimed: BigUint<M>,
+    pub volume: BigUint<M>,
+    pub transfer_count: u64,
+}
+
+impl<M: ManagedTypeApi> Default for FeeStats<M> {
+    fn default() -> Self {
+        FeeStats {
+            fees_collected: BigUint::zero(),
+            fees_claimed: BigUint::zero(),
+            volume: BigUint::zero(),
+            transfer_count: 0,
+        }
+    }
+}
+
+#[multiversx_sc::module]
+pub trait FeeStatsModule {
+    fn record_fee_collected(&self, token: &TokenIdentifier, nonce: u64, amount: &BigUint) {
+        self.update_fee_stats(token, nonce, |stats| stats.fees_collected += amount);
+    }
+
+    fn record_fees_claimed(&self, fees: &ManagedVec<EsdtTokenPayment>) {
+        for fee in fees.iter() {
+            self.update_fee_stats(&fee.token_identifier, fee.token_nonce, |stats| {
+                stats.fees_claimed += &fee.amount
+            });
+        }
+    }
+
+    fn record_transfer(&self, payment: &EsdtTokenPayment) {
+        self.update_fee_stats(&payment.token_identifier, payment.token_nonce, |stats| {
+            stats.volume += &payment.amount;
+            stats.transfer_count += 1;
+        });
+    }
+
+    fn update_fee_stats<F: FnOnce(&mut FeeStats<Self::Api>)>(
+        &self,
+        token: &TokenIdentifier,
+        nonce: u64,
+        f: F,
+    ) {
+        let mut stats = self.get_fee_stats(token, nonce);
+        f(&mut stats);
+        self.fee_stats(token, nonce).set(stats);
+    }
+
+    #[view(getFeeStats)]
+    fn get_fee_stats(&self, token: &TokenIdentifier, nonce: u64) -> FeeStats<Self::Api> {
+        let stats_mapper = self.fee_stats(token, nonce);
+        if stats_mapper.is_empty() {
+            return FeeStats::default();
+        }
+        stats_mapper.get()
+    }
+
+    #[storage_mapper("fee_stats")]
+    fn fee_stats(
+        &self,
+        token: &TokenIdentifier,
+        nonce: u64,
+    ) -> SingleValueMapper<FeeStats<Self::Api>>;
+}
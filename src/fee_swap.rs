@@ -0,0 +1,89 @@
+use multiversx_sc::imports::*;
+
+const SWAP_FIXED_OUTPUT_ENDPOINT: &str = "swapTokensFixedOutput";
+
+#[multiversx_sc::module]
+pub trait FeeSwapModule {
+    #[only_owner]
+    #[endpoint(setFeeSwapPair)]
+    fn set_fee_swap_pair(
+        &self,
+        input_token: TokenIdentifier,
+        fee_token: TokenIdentifier,
+        pair_address: ManagedAddress,
+    ) {
+        require!(
+            self.blockchain().is_smart_contract(&pair_address),
+            "Invalid pair address"
+        );
+        self.fee_swap_pair(&input_token, &fee_token)
+            .set(pair_address);
+    }
+
+    #[only_owner]
+    #[endpoint(removeFeeSwapPair)]
+    fn remove_fee_swap_pair(&self, input_token: TokenIdentifier, fee_token: TokenIdentifier) {
+        self.fee_swap_pair(&input_token, &fee_token).clear();
+    }
+
+    fn can_swap_into_fee(&self, payment: &EsdtTokenPayment, fee: &EsdtTokenPayment) -> bool {
+        payment.token_nonce == 0
+            && !self
+                .fee_swap_pair(&payment.token_identifier, &fee.token_identifier)
+                .is_empty()
+    }
+
+    // the whole attached payment is the maximum input the caller accepts for the swap,
+    // the unspent part is added to the refunds
+    fn swap_into_fee(
+        &self,
+        payment: EsdtTokenPayment,
+        fee: &EsdtTokenPayment,
+        refunds: &mut ManagedVec<EsdtTokenPayment>,
+    ) -> EsdtTokenPayment {
+        require!(self.can_swap_into_fee(&payment, fee), "Wrong fee token");
+        let pair_address = self
+            .fee_swap_pair(&payment.token_identifier, &fee.token_identifier)
+            .get();
+
+        let back_transfers = self
+            .tx()
+            .to(pair_address)
+            .raw_call(SWAP_FIXED_OUTPUT_ENDPOINT)
+            .argument(&fee.token_identifier)
+            .argument(&fee.amount)
+            .payment(&payment)
+            .returns(ReturnsBackTransfers)
+            .sync_call();
+
+        let mut swapped_amount = BigUint::zero();
+        for back_transfer in back_transfers.esdt_payments.iter() {
+            if back_transfer.token_identifier == fee.token_identifier {
+                swapped_amount += &back_transfer.amount;
+            } else {
+                refunds.push(back_transfer);
+            }
+        }
+        require!(
+            swapped_amount >= fee.amount,
+            "Mismatching payment for covering fees"
+        );
+        if swapped_amount > fee.amount {
+            refunds.push(EsdtTokenPayment::new(
+                fee.token_identifier.clone(),
+                fee.token_nonce,
+                swapped_amount - &fee.amount,
+            ));
+        }
+
+        fee.clone()
+    }
+
+    #[view(getFeeSwapPair)]
+    #[storage_mapper("fee_swap_pair")]
+    fn fee_swap_pair(
+        &self,
+        input_token: &TokenIdentifier,
+        fee_token: &TokenIdentifier,
+    ) -> SingleValueMapper<ManagedAddress>;
+}
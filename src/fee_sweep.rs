@@ -0,0 +1,48 @@
+use multiversx_sc::imports::*;
+
+#[multiversx_sc::module]
+pub trait FeeSweepModule: crate::fee_stats::FeeStatsModule {
+    #[only_owner]
+    #[endpoint(setTreasury)]
+    fn set_treasury(&self, address: ManagedAddress) {
+        require!(!address.is_zero(), "Invalid treasury");
+        self.treasury().set(address);
+    }
+
+    // a zero threshold disables the sweep of the token
+    #[only_owner]
+    #[endpoint(setSweepThreshold)]
+    fn set_sweep_threshold(&self, token: TokenIdentifier, threshold: BigUint) {
+        self.sweep_threshold(&token).set(threshold);
+    }
+
+    fn is_sweep_due(&self, token: &TokenIdentifier, accrued_amount: &BigUint) -> bool {
+        let threshold = self.sweep_threshold(token).get();
+        threshold > 0 && *accrued_amount >= threshold && !self.treasury().is_empty()
+    }
+
+    fn sweep_fees(&self, token: &TokenIdentifier, nonce: u64, amount: BigUint) {
+        let fees =
+            ManagedVec::from_single_item(EsdtTokenPayment::new(token.clone(), nonce, amount));
+        self.record_fees_claimed(&fees);
+
+        let treasury = self.treasury().get();
+        self.fee_sweep_event(&treasury, &fees);
+        self.tx().to(&treasury).payment(&fees).transfer();
+    }
+
+    #[event("fee_sweep")]
+    fn fee_sweep_event(
+        &self,
+        #[indexed] treasury: &ManagedAddress,
+        fees: &ManagedVec<EsdtTokenPayment>,
+    );
+
+    #[view(getTreasury)]
+    #[storage_mapper("treasury")]
+    fn treasury(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getSweepThreshold)]
+    #[storage_mapper("sweep_threshold")]
+    fn sweep_threshold(&self, token: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+}
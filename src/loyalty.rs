@@ -0,0 +1,78 @@
+use multiversx_sc::imports::*;
+
+use crate::fee::PERCENTAGE_DIVISOR;
+
+#[multiversx_sc::module]
+pub trait LoyaltyModule {
+    // nonce 0 sets the discount of the whole collection, other nonces override it
+    #[only_owner]
+    #[endpoint(setLoyaltyDiscount)]
+    fn set_loyalty_discount(&self, collection: TokenIdentifier, nonce: u64, discount: u32) {
+        require!(discount <= PERCENTAGE_DIVISOR, "Invalid discount");
+        self.loyalty_discount(&collection, nonce).set(discount);
+    }
+
+    // the registered loyalty tokens give their discount to all the transfers of the sender
+    #[payable("*")]
+    #[endpoint(registerLoyaltyTokens)]
+    fn register_loyalty_tokens(&self) {
+        require!(
+            *self.call_value().egld_value() == 0,
+            "EGLD transfers not allowed"
+        );
+        let caller = self.blockchain().get_caller();
+        let mut loyalty_tokens = self.loyalty_tokens(&caller);
+        for payment in self.call_value().all_esdt_transfers().iter() {
+            require!(
+                self.get_token_loyalty_discount(&payment.token_identifier, payment.token_nonce) > 0,
+                "Not a loyalty token"
+            );
+            loyalty_tokens
+                .entry((payment.token_identifier, payment.token_nonce))
+                .or_insert(0u64.into())
+                .update(|value| *value += payment.amount);
+        }
+    }
+
+    #[endpoint(withdrawLoyaltyTokens)]
+    fn withdraw_loyalty_tokens(&self) {
+        let caller = self.blockchain().get_caller();
+        let mut loyalty_tokens = self.loyalty_tokens(&caller);
+        require!(!loyalty_tokens.is_empty(), "There is nothing to withdraw");
+        let mut payments = ManagedVec::<Self::Api, EsdtTokenPayment>::new();
+        for ((token, nonce), amount) in loyalty_tokens.iter() {
+            payments.push(EsdtTokenPayment::new(token, nonce, amount));
+        }
+        loyalty_tokens.clear();
+
+        self.tx().to(&caller).payment(&payments).transfer();
+    }
+
+    fn get_token_loyalty_discount(&self, token: &TokenIdentifier, nonce: u64) -> u32 {
+        if nonce == 0 {
+            return 0;
+        }
+        let nonce_discount = self.loyalty_discount(token, nonce);
+        if !nonce_discount.is_empty() {
+            return nonce_discount.get();
+        }
+        self.loyalty_discount(token, 0).get()
+    }
+
+    // the biggest discount of the sender's registered loyalty tokens
+    fn get_loyalty_discount(&self, address: &ManagedAddress) -> u32 {
+        let mut discount = 0;
+        for (token, nonce) in self.loyalty_tokens(address).keys() {
+            discount = discount.max(self.get_token_loyalty_discount(&token, nonce));
+        }
+        discount
+    }
+
+    #[view(getLoyaltyDiscount)]
+    #[storage_mapper("loyalty_discount")]
+    fn loyalty_discount(&self, collection: &TokenIdentifier, nonce: u64) -> SingleValueMapper<u32>;
+
+    #[view(getLoyaltyTokens)]
+    #[storage_mapper("loyalty_tokens")]
+    fn loyalty_tokens(&self, owner: &ManagedAddress) -> MapMapper<(TokenIdentifier, u64), BigUint>;
+}
@@ -0,0 +1,140 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::fee::PERCENTAGE_DIVISOR;
+
+// signed off-chain by the sender, for fixed fees `fee` is the attached fee payment, a zero amount
+// meaning none, for fees taken out of the payment it is the most the sender agrees to pay
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct Permit<M: ManagedTypeApi> {
+    pub recipient: ManagedAddress<M>,
+    pub payment: EsdtTokenPayment<M>,
+    pub fee: EsdtTokenPayment<M>,
+    pub nonce: u64,
+    pub deadline: u64,
+}
+
+#[multiversx_sc::module]
+pub trait PermitModule: crate::fee_stats::FeeStatsModule {
+    #[payable("*")]
+    #[endpoint]
+    fn deposit(&self) {
+        require!(
+            *self.call_value().egld_value() == 0,
+            "EGLD transfers not allowed"
+        );
+        let caller = self.blockchain().get_caller();
+        for payment in self.call_value().all_esdt_transfers().iter() {
+            self.credit_deposit(&caller, &payment);
+        }
+    }
+
+    #[endpoint(withdrawDeposits)]
+    fn withdraw_deposits(&self) {
+        let caller = self.blockchain().get_caller();
+        let mut deposits = self.deposits(&caller);
+        require!(!deposits.is_empty(), "There is nothing to withdraw");
+        let mut payments = ManagedVec::<Self::Api, EsdtTokenPayment>::new();
+        for ((token, nonce), amount) in deposits.iter() {
+            payments.push(EsdtTokenPayment::new(token, nonce, amount));
+        }
+        deposits.clear();
+
+        self.tx().to(&caller).payment(&payments).transfer();
+    }
+
+    #[only_owner]
+    #[endpoint(setRelayerShare)]
+    fn set_relayer_share(&self, share: u32) {
+        require!(share <= PERCENTAGE_DIVISOR, "Invalid relayer share");
+        self.relayer_share().set(share);
+    }
+
+    // the signed message is the contract address followed by the encoded permit
+    fn use_permit(
+        &self,
+        signer: &ManagedAddress,
+        permit: &Permit<Self::Api>,
+        signature: &ManagedBuffer,
+    ) {
+        require!(
+            permit.deadline >= self.blockchain().get_block_timestamp(),
+            "Permit expired"
+        );
+        let nonce_mapper = self.permit_nonce(signer);
+        require!(permit.nonce == nonce_mapper.get(), "Invalid permit nonce");
+        nonce_mapper.set(permit.nonce + 1);
+
+        let mut message = self
+            .blockchain()
+            .get_sc_address()
+            .as_managed_buffer()
+            .clone();
+        message.append(&self.serializer().top_encode_to_managed_buffer(permit));
+        self.crypto()
+            .verify_ed25519(signer.as_managed_buffer(), &message, signature);
+    }
+
+    fn credit_deposit(&self, owner: &ManagedAddress, payment: &EsdtTokenPayment) {
+        if payment.amount == 0 {
+            return;
+        }
+        self.deposits(owner)
+            .entry((payment.token_identifier.clone(), payment.token_nonce))
+            .or_insert(0u64.into())
+            .update(|value| *value += &payment.amount);
+    }
+
+    fn debit_deposit(&self, owner: &ManagedAddress, payment: &EsdtTokenPayment) {
+        require!(
+            self.try_debit_deposit(owner, payment),
+            "Insufficient deposit"
+        );
+    }
+
+    // takes the payment out of the deposit, if it is big enough
+    fn try_debit_deposit(&self, owner: &ManagedAddress, payment: &EsdtTokenPayment) -> bool {
+        let mut deposits = self.deposits(owner);
+        let key = (payment.token_identifier.clone(), payment.token_nonce);
+        let balance = deposits.get(&key).unwrap_or_default();
+        if balance < payment.amount {
+            return false;
+        }
+
+        if balance == payment.amount {
+            deposits.remove(&key);
+        } else {
+            deposits.insert(key, balance - &payment.amount);
+        }
+        true
+    }
+
+    // returns the part of the fee credited to the relayer's deposit
+    fn credit_relayer_share(
+        &self,
+        relayer: &ManagedAddress,
+        token: &TokenIdentifier,
+        nonce: u64,
+        fee_amount: &BigUint,
+    ) -> BigUint {
+        let relayer_amount = fee_amount * self.relayer_share().get() / PERCENTAGE_DIVISOR;
+        let relayer_payment = EsdtTokenPayment::new(token.clone(), nonce, relayer_amount.clone());
+        // the share leaves the fees once it is in the relayer's deposit, so it counts as claimed
+        self.record_fees_claimed(&ManagedVec::from_single_item(relayer_payment.clone()));
+        self.credit_deposit(relayer, &relayer_payment);
+        relayer_amount
+    }
+
+    #[view(getDeposits)]
+    #[storage_mapper("deposits")]
+    fn deposits(&self, owner: &ManagedAddress) -> MapMapper<(TokenIdentifier, u64), BigUint>;
+
+    #[view(getPermitNonce)]
+    #[storage_mapper("permit_nonce")]
+    fn permit_nonce(&self, signer: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    #[view(getRelayerShare)]
+    #[storage_mapper("relayer_share")]
+    fn relayer_share(&self) -> SingleValueMapper<u32>;
+}
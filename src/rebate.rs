@@ -0,0 +1,169 @@
+use multiversx_sc::imports::*;
+
+use crate::fee::PERCENTAGE_DIVISOR;
+
+// each epoch has its own rebate pool per fee token, shared by the senders proportionally
+// to the fees they paid in that epoch, a sender's share is settled lazily once the epoch ended
+#[multiversx_sc::module]
+pub trait RebateModule: crate::fee_stats::FeeStatsModule {
+    #[only_owner]
+    #[endpoint(setRebateShare)]
+    fn set_rebate_share(&self, share: u32) {
+        require!(share <= PERCENTAGE_DIVISOR, "Invalid rebate share");
+        self.rebate_share().set(share);
+    }
+
+    #[endpoint(claimRebates)]
+    fn claim_rebates(&self) {
+        let caller = self.blockchain().get_caller();
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut ended_weights = ManagedVec::<Self::Api, EsdtTokenPayment>::new();
+        for ((token, nonce), (epoch, weight)) in self.rebate_weights(&caller).iter() {
+            if epoch < current_epoch {
+                ended_weights.push(EsdtTokenPayment::new(token, nonce, weight));
+            }
+        }
+        for ended_weight in ended_weights.iter() {
+            self.settle_rebate(
+                &caller,
+                &ended_weight.token_identifier,
+                ended_weight.token_nonce,
+            );
+        }
+
+        let mut rebates_mapper = self.rebates(&caller);
+        require!(!rebates_mapper.is_empty(), "There is nothing to claim");
+        let mut rebates = ManagedVec::<Self::Api, EsdtTokenPayment>::new();
+        for ((token, nonce), amount) in rebates_mapper.iter() {
+            rebates.push(EsdtTokenPayment::new(token, nonce, amount));
+        }
+        rebates_mapper.clear();
+        self.record_fees_claimed(&rebates);
+
+        self.tx().to(&caller).payment(&rebates).transfer();
+    }
+
+    // returns the part of the fee added to the rebate pool, the whole paid fee counts as the sender's weight
+    fn credit_rebate_share(
+        &self,
+        sender: &ManagedAddress,
+        token: &TokenIdentifier,
+        nonce: u64,
+        paid_fee: &BigUint,
+        fee_amount: &BigUint,
+    ) -> BigUint {
+        let share = self.rebate_share().get();
+        if share == 0 {
+            return BigUint::zero();
+        }
+
+        self.settle_rebate(sender, token, nonce);
+        let epoch = self.blockchain().get_block_epoch();
+        let rebate_amount = fee_amount * share / PERCENTAGE_DIVISOR;
+        self.rebate_pool(token, nonce, epoch)
+            .update(|value| *value += &rebate_amount);
+        self.rebate_pool_weight(token, nonce, epoch)
+            .update(|value| *value += paid_fee);
+        self.rebate_weights(sender)
+            .entry((token.clone(), nonce))
+            .or_insert((epoch, BigUint::zero()))
+            .update(|(_, weight)| *weight += paid_fee);
+        rebate_amount
+    }
+
+    // moves the sender's share of an ended epoch pool to the claimable rebates,
+    // the last sender to settle gets the rounding remainder of the pool
+    fn settle_rebate(&self, sender: &ManagedAddress, token: &TokenIdentifier, nonce: u64) {
+        let mut weights = self.rebate_weights(sender);
+        let key = (token.clone(), nonce);
+        let (epoch, weight) = match weights.get(&key) {
+            Some(epoch_weight) => epoch_weight,
+            None => return,
+        };
+        if epoch == self.blockchain().get_block_epoch() {
+            return;
+        }
+        weights.remove(&key);
+
+        let pool_mapper = self.rebate_pool(token, nonce, epoch);
+        let pool_weight_mapper = self.rebate_pool_weight(token, nonce, epoch);
+        let pool = pool_mapper.get();
+        let pool_weight = pool_weight_mapper.get();
+        let rebate_amount = &pool * &weight / &pool_weight;
+        pool_mapper.set(&pool - &rebate_amount);
+        pool_weight_mapper.set(&pool_weight - &weight);
+        if rebate_amount == 0 {
+            return;
+        }
+
+        self.rebates(sender)
+            .entry(key)
+            .or_insert(0u64.into())
+            .update(|value| *value += &rebate_amount);
+    }
+
+    // the settled rebates together with the shares of the ended epochs not yet settled
+    #[view(getClaimableRebates)]
+    fn get_claimable_rebates(
+        &self,
+        address: ManagedAddress,
+    ) -> MultiValueEncoded<EsdtTokenPayment> {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut rebates = ManagedVec::<Self::Api, EsdtTokenPayment>::new();
+        for ((token, nonce), amount) in self.rebates(&address).iter() {
+            rebates.push(EsdtTokenPayment::new(token, nonce, amount));
+        }
+        for ((token, nonce), (epoch, weight)) in self.rebate_weights(&address).iter() {
+            if epoch == current_epoch {
+                continue;
+            }
+            let rebate_amount = self.rebate_pool(&token, nonce, epoch).get() * weight
+                / self.rebate_pool_weight(&token, nonce, epoch).get();
+            let position = rebates
+                .iter()
+                .position(|rebate| rebate.token_identifier == token && rebate.token_nonce == nonce);
+            match position {
+                Some(index) => rebates.get_mut(index).amount += rebate_amount,
+                None if rebate_amount > 0 => {
+                    rebates.push(EsdtTokenPayment::new(token, nonce, rebate_amount));
+                }
+                None => {}
+            }
+        }
+        rebates.into()
+    }
+
+    #[view(getRebateShare)]
+    #[storage_mapper("rebate_share")]
+    fn rebate_share(&self) -> SingleValueMapper<u32>;
+
+    #[view(getRebatePool)]
+    #[storage_mapper("rebate_pool")]
+    fn rebate_pool(
+        &self,
+        token: &TokenIdentifier,
+        nonce: u64,
+        epoch: u64,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[view(getRebatePoolWeight)]
+    #[storage_mapper("rebate_pool_weight")]
+    fn rebate_pool_weight(
+        &self,
+        token: &TokenIdentifier,
+        nonce: u64,
+        epoch: u64,
+    ) -> SingleValueMapper<BigUint>;
+
+    // the fees paid by the sender in their last active epoch, per fee token
+    #[view(getRebateWeights)]
+    #[storage_mapper("rebate_weights")]
+    fn rebate_weights(
+        &self,
+        sender: &ManagedAddress,
+    ) -> MapMapper<(TokenIdentifier, u64), (u64, BigUint)>;
+
+    #[view(getRebates)]
+    #[storage_mapper("rebates")]
+    fn rebates(&self, sender: &ManagedAddress) -> MapMapper<(TokenIdentifier, u64), BigUint>;
+}
@@ -0,0 +1,57 @@
+use multiversx_sc::imports::*;
+
+#[multiversx_sc::module]
+pub trait RecipientPolicyModule {
+    #[only_owner]
+    #[endpoint(addDeniedRecipients)]
+    fn add_denied_recipients(&self, recipients: MultiValueEncoded<ManagedAddress>) {
+        let mut denied_recipients = self.denied_recipients();
+        for recipient in recipients {
+            denied_recipients.insert(recipient);
+        }
+    }
+
+    #[only_owner]
+    #[endpoint(removeDeniedRecipients)]
+    fn remove_denied_recipients(&self, recipients: MultiValueEncoded<ManagedAddress>) {
+        let mut denied_recipients = self.denied_recipients();
+        for recipient in recipients {
+            denied_recipients.swap_remove(&recipient);
+        }
+    }
+
+    #[only_owner]
+    #[endpoint(setPayableRecipientRequired)]
+    fn set_payable_recipient_required(&self, required: bool) {
+        self.payable_recipient_required().set(required);
+    }
+
+    // tokens sent to the contract itself would be stuck outside of the fee accounting
+    fn require_recipient_allowed(&self, recipient: &ManagedAddress) {
+        require!(!recipient.is_zero(), "Invalid recipient");
+        require!(
+            *recipient != self.blockchain().get_sc_address(),
+            "Cannot transfer to the contract itself"
+        );
+        require!(
+            !self.denied_recipients().contains(recipient),
+            "Recipient not allowed"
+        );
+        if self.payable_recipient_required().get() && self.blockchain().is_smart_contract(recipient)
+        {
+            let code_metadata = self.blockchain().get_code_metadata(recipient);
+            require!(
+                code_metadata.is_payable() || code_metadata.is_payable_by_sc(),
+                "Recipient contract is not payable"
+            );
+        }
+    }
+
+    #[view(getDeniedRecipients)]
+    #[storage_mapper("denied_recipients")]
+    fn denied_recipients(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(isPayableRecipientRequired)]
+    #[storage_mapper("payable_recipient_required")]
+    fn payable_recipient_required(&self) -> SingleValueMapper<bool>;
+}
@@ -0,0 +1,81 @@
+use multiversx_sc::imports::*;
+
+use crate::fee::PERCENTAGE_DIVISOR;
+
+#[multiversx_sc::module]
+pub trait ReferralModule: crate::fee_stats::FeeStatsModule {
+    #[only_owner]
+    #[endpoint(addReferrers)]
+    fn add_referrers(&self, referrers: MultiValueEncoded<ManagedAddress>) {
+        let mut referrers_mapper = self.referrers();
+        for referrer in referrers {
+            referrers_mapper.insert(referrer);
+        }
+    }
+
+    #[only_owner]
+    #[endpoint(removeReferrers)]
+    fn remove_referrers(&self, referrers: MultiValueEncoded<ManagedAddress>) {
+        let mut referrers_mapper = self.referrers();
+        for referrer in referrers {
+            referrers_mapper.swap_remove(&referrer);
+        }
+    }
+
+    #[only_owner]
+    #[endpoint(setReferralShare)]
+    fn set_referral_share(&self, share: u32) {
+        require!(share <= PERCENTAGE_DIVISOR, "Invalid referral share");
+        self.referral_share().set(share);
+    }
+
+    #[endpoint(claimReferralFees)]
+    fn claim_referral_fees(&self) {
+        let caller = self.blockchain().get_caller();
+        let mut referral_fees = self.referral_fees(&caller);
+        require!(!referral_fees.is_empty(), "There is nothing to claim");
+        let mut fees = ManagedVec::<Self::Api, EsdtTokenPayment>::new();
+        for ((token, nonce), amount) in referral_fees.iter() {
+            fees.push(EsdtTokenPayment::new(token, nonce, amount));
+        }
+        referral_fees.clear();
+        self.record_fees_claimed(&fees);
+
+        self.tx().to(&caller).payment(&fees).transfer();
+    }
+
+    // returns the part of the fee credited to the referrer
+    fn credit_referral_share(
+        &self,
+        referrer: &ManagedAddress,
+        token: &TokenIdentifier,
+        nonce: u64,
+        fee_amount: &BigUint,
+    ) -> BigUint {
+        let referral_amount = fee_amount * self.referral_share().get() / PERCENTAGE_DIVISOR;
+        if referral_amount == 0 {
+            return referral_amount;
+        }
+
+        self.referral_fees(referrer)
+            .entry((token.clone(), nonce))
+            .or_insert(0u64.into())
+            .update(|value| *value += &referral_amount);
+        referral_amount
+    }
+
+    #[view(getReferrers)]
+    #[storage_mapper("referrers")]
+    fn referrers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getReferralShare)]
+    #[storage_mapper("referral_share")]
+    fn referral_share(&self) -> SingleValueMapper<u32>;
+
+    #[view(getReferralFees)]
+    #[storage_mapper("referral_fees")]
+    fn referral_fees(
+        &self,
+        referrer: &ManagedAddress,
+    ) -> MapMapper<(TokenIdentifier, u64), BigUint>;
+}
@@ -0,0 +1,108 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::fee::FeeBeneficiaries;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct PendingTransfer<M: ManagedTypeApi> {
+    pub from: ManagedAddress<M>,
+    pub to: ManagedAddress<M>,
+    pub memo: ManagedBuffer<M>,
+    pub payments: ManagedVec<M, EsdtTokenPayment<M>>,
+    pub fees: ManagedVec<M, EsdtTokenPayment<M>>,
+    pub referrer: Option<ManagedAddress<M>>,
+    pub relayer: Option<ManagedAddress<M>>,
+    pub release_round: u64,
+}
+
+impl<M: ManagedTypeApi> PendingTransfer<M> {
+    pub fn beneficiaries(&self) -> FeeBeneficiaries<M> {
+        FeeBeneficiaries {
+            sender: Some(self.from.clone()),
+            referrer: self.referrer.clone(),
+            relayer: self.relayer.clone(),
+        }
+    }
+}
+
+#[multiversx_sc::module]
+pub trait SettlementModule {
+    // a zero delay settles the transfers of the token immediately
+    #[only_owner]
+    #[endpoint(setSettlementDelay)]
+    fn set_settlement_delay(&self, token: TokenIdentifier, rounds: u64) {
+        self.settlement_delay(&token).set(rounds);
+    }
+
+    #[only_owner]
+    #[endpoint(setReversalFeeRefund)]
+    fn set_reversal_fee_refund(&self, enabled: bool) {
+        self.reversal_fee_refund().set(enabled);
+    }
+
+    // the longest delay of the transfered tokens
+    fn get_settlement_delay(&self, payments: &ManagedVec<EsdtTokenPayment>) -> u64 {
+        payments
+            .iter()
+            .map(|payment| self.settlement_delay(&payment.token_identifier).get())
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn hold_transfer(&self, pending: PendingTransfer<Self::Api>) {
+        let id = self.last_pending_transfer_id().update(|id| {
+            *id += 1;
+            *id
+        });
+        self.pending_transfer_event(
+            id,
+            &pending.from,
+            &pending.to,
+            pending.release_round,
+            &pending.payments,
+        );
+        self.pending_transfers(id).set(pending);
+    }
+
+    fn take_pending_transfer(&self, id: u64) -> PendingTransfer<Self::Api> {
+        let pending_mapper = self.pending_transfers(id);
+        require!(!pending_mapper.is_empty(), "Unknown pending transfer");
+        let pending = pending_mapper.get();
+        pending_mapper.clear();
+        pending
+    }
+
+    #[event("pending_transfer")]
+    fn pending_transfer_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] from: &ManagedAddress,
+        #[indexed] to: &ManagedAddress,
+        #[indexed] release_round: u64,
+        payments: &ManagedVec<EsdtTokenPayment>,
+    );
+
+    #[event("transfer_reversed")]
+    fn transfer_reversed_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] from: &ManagedAddress,
+        refunds: &ManagedVec<EsdtTokenPayment>,
+    );
+
+    #[view(getSettlementDelay)]
+    #[storage_mapper("settlement_delay")]
+    fn settlement_delay(&self, token: &TokenIdentifier) -> SingleValueMapper<u64>;
+
+    #[view(isReversalFeeRefundEnabled)]
+    #[storage_mapper("reversal_fee_refund")]
+    fn reversal_fee_refund(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("last_pending_transfer_id")]
+    fn last_pending_transfer_id(&self) -> SingleValueMapper<u64>;
+
+    #[view(getPendingTransfer)]
+    #[storage_mapper("pending_transfers")]
+    fn pending_transfers(&self, id: u64) -> SingleValueMapper<PendingTransfer<Self::Api>>;
+}
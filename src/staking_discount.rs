@@ -0,0 +1,83 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::fee::PERCENTAGE_DIVISOR;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem)]
+pub struct StakingTier<M: ManagedTypeApi> {
+    pub min_stake: BigUint<M>,
+    pub discount: u32,
+}
+
+#[multiversx_sc::module]
+pub trait StakingDiscountModule {
+    // the view gets the sender address as its only argument and returns the staked amount
+    #[only_owner]
+    #[endpoint(setStakingContract)]
+    fn set_staking_contract(&self, address: ManagedAddress, view_name: ManagedBuffer) {
+        require!(
+            self.blockchain().is_smart_contract(&address),
+            "Invalid staking contract"
+        );
+        self.staking_contract().set(address);
+        self.staking_view().set(view_name);
+    }
+
+    #[only_owner]
+    #[endpoint(removeStakingContract)]
+    fn remove_staking_contract(&self) {
+        self.staking_contract().clear();
+        self.staking_view().clear();
+    }
+
+    #[only_owner]
+    #[endpoint(setStakingDiscountTiers)]
+    fn set_staking_discount_tiers(&self, tiers: MultiValueEncoded<MultiValue2<BigUint, u32>>) {
+        let mut staking_tiers = ManagedVec::new();
+        for tier in tiers {
+            let (min_stake, discount) = tier.into_tuple();
+            require!(discount <= PERCENTAGE_DIVISOR, "Invalid discount");
+            staking_tiers.push(StakingTier {
+                min_stake,
+                discount,
+            });
+        }
+        self.staking_discount_tiers().set(staking_tiers);
+    }
+
+    // the best discount of the tiers reached by the stake of the address
+    fn get_staking_discount(&self, address: &ManagedAddress) -> u32 {
+        let tiers = self.staking_discount_tiers().get();
+        if tiers.is_empty() || self.staking_contract().is_empty() {
+            return 0;
+        }
+
+        let staked_amount = self
+            .tx()
+            .to(self.staking_contract().get())
+            .raw_call(self.staking_view().get())
+            .argument(address)
+            .original_result::<BigUint>()
+            .returns(ReturnsResult)
+            .sync_call();
+        tiers
+            .iter()
+            .filter(|tier| tier.min_stake <= staked_amount)
+            .map(|tier| tier.discount)
+            .max()
+            .unwrap_or_default()
+    }
+
+    #[view(getStakingContract)]
+    #[storage_mapper("staking_contract")]
+    fn staking_contract(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getStakingView)]
+    #[storage_mapper("staking_view")]
+    fn staking_view(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getStakingDiscountTiers)]
+    #[storage_mapper("staking_discount_tiers")]
+    fn staking_discount_tiers(&self) -> SingleValueMapper<ManagedVec<StakingTier<Self::Api>>>;
+}
@@ -0,0 +1,91 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Eq, Clone, Copy)]
+pub enum TokenPolicy {
+    Open,
+    AllowlistOnly,
+    Denylist,
+}
+
+#[multiversx_sc::module]
+pub trait TokenPolicyModule {
+    #[only_owner]
+    #[endpoint(setTokenPolicy)]
+    fn set_token_policy(&self, policy: TokenPolicy) {
+        self.token_policy().set(policy);
+    }
+
+    #[only_owner]
+    #[endpoint(addPolicyTokens)]
+    fn add_policy_tokens(&self, tokens: MultiValueEncoded<TokenIdentifier>) {
+        let mut policy_tokens = self.policy_tokens();
+        for token in tokens {
+            policy_tokens.insert(token);
+        }
+    }
+
+    #[only_owner]
+    #[endpoint(removePolicyTokens)]
+    fn remove_policy_tokens(&self, tokens: MultiValueEncoded<TokenIdentifier>) {
+        let mut policy_tokens = self.policy_tokens();
+        for token in tokens {
+            policy_tokens.swap_remove(&token);
+        }
+    }
+
+    // a zero maximum means no maximum
+    #[only_owner]
+    #[endpoint(setTransferLimits)]
+    fn set_transfer_limits(
+        &self,
+        token: TokenIdentifier,
+        min_amount: BigUint,
+        max_amount: BigUint,
+    ) {
+        require!(
+            max_amount == 0 || min_amount <= max_amount,
+            "Invalid transfer limits"
+        );
+        self.transfer_limits(&token).set((min_amount, max_amount));
+    }
+
+    fn require_token_allowed(&self, token: &TokenIdentifier) {
+        let listed = self.policy_tokens().contains(token);
+        let allowed = match self.token_policy().get() {
+            TokenPolicy::Open => true,
+            TokenPolicy::AllowlistOnly => listed,
+            TokenPolicy::Denylist => !listed,
+        };
+        require!(allowed, "Token not allowed");
+    }
+
+    fn require_transfer_limits(&self, payment: &EsdtTokenPayment) {
+        let limits_mapper = self.transfer_limits(&payment.token_identifier);
+        if limits_mapper.is_empty() {
+            return;
+        }
+        let (min_amount, max_amount) = limits_mapper.get();
+        require!(
+            payment.amount >= min_amount,
+            "Transfer amount below minimum"
+        );
+        require!(
+            max_amount == 0 || payment.amount <= max_amount,
+            "Transfer amount above maximum"
+        );
+    }
+
+    #[view(getTokenPolicy)]
+    #[storage_mapper("token_policy")]
+    fn token_policy(&self) -> SingleValueMapper<TokenPolicy>;
+
+    #[view(getPolicyTokens)]
+    #[storage_mapper("policy_tokens")]
+    fn policy_tokens(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    #[view(getTransferLimits)]
+    #[storage_mapper("transfer_limits")]
+    fn transfer_limits(&self, token: &TokenIdentifier) -> SingleValueMapper<(BigUint, BigUint)>;
+}
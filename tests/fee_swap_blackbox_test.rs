@@ -0,0 +1,207 @@
+use esdt_transfer_with_fee::esdt_transfer_with_fee_proxy;
+use multiversx_sc_scenario::imports::*;
+
+const CODE_PATH: MxscPath = MxscPath::new("output/esdt-transfer-with-fee.mxsc.json");
+const PAIR_MOCK_CODE_PATH: MxscPath = MxscPath::new("output/pair-mock.mxsc.json");
+
+const OWNER: TestAddress = TestAddress::new("owner");
+const USER: TestAddress = TestAddress::new("user");
+const RECEIVER: TestAddress = TestAddress::new("receiver");
+const SC_ADDRESS: TestSCAddress = TestSCAddress::new("esdt-transfer-with-fee");
+const PAIR_ADDRESS: TestSCAddress = TestSCAddress::new("pair");
+
+const TRANSFERRED_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("TKN-123456");
+const FEE_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("USDC-aaaaaa");
+const SWAPPED_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("WEGLD-012345");
+const FEE_AMOUNT: u64 = 10;
+
+mod pair_mock {
+    use multiversx_sc::imports::*;
+
+    // fixed price: 2 units of the input token for each unit of the output token
+    pub const INPUT_PER_OUTPUT: u64 = 2;
+
+    #[multiversx_sc::contract]
+    pub trait PairMock {
+        #[init]
+        fn init(&self) {}
+
+        #[payable("*")]
+        #[endpoint(swapTokensFixedOutput)]
+        fn swap_tokens_fixed_output(&self, token_out: TokenIdentifier, amount_out: BigUint) {
+            let payment = self.call_value().single_esdt();
+            let amount_in = &amount_out * INPUT_PER_OUTPUT;
+            require!(payment.amount >= amount_in, "Slippage exceeded");
+
+            let mut payments = ManagedVec::<Self::Api, EsdtTokenPayment>::new();
+            payments.push(EsdtTokenPayment::new(token_out, 0, amount_out));
+            if payment.amount > amount_in {
+                payments.push(EsdtTokenPayment::new(
+                    payment.token_identifier.clone(),
+                    0,
+                    &payment.amount - &amount_in,
+                ));
+            }
+            self.tx().to(ToCaller).payment(&payments).transfer();
+        }
+    }
+}
+
+fn world() -> ScenarioWorld {
+    let mut blockchain = ScenarioWorld::new();
+
+    blockchain.register_contract(CODE_PATH, esdt_transfer_with_fee::ContractBuilder);
+    blockchain.register_contract(PAIR_MOCK_CODE_PATH, pair_mock::ContractBuilder);
+    blockchain
+}
+
+fn setup() -> ScenarioWorld {
+    let mut world = world();
+
+    world.account(OWNER).nonce(1);
+    world
+        .account(USER)
+        .nonce(1)
+        .esdt_balance(TRANSFERRED_TOKEN, 500)
+        .esdt_balance(FEE_TOKEN, 500)
+        .esdt_balance(SWAPPED_TOKEN, 500);
+    world.account(RECEIVER).nonce(1);
+    world
+        .account(PAIR_ADDRESS)
+        .nonce(1)
+        .code(PAIR_MOCK_CODE_PATH)
+        .owner(OWNER)
+        .esdt_balance(FEE_TOKEN, 1_000);
+
+    world
+        .tx()
+        .from(OWNER)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .init()
+        .code(CODE_PATH)
+        .new_address(SC_ADDRESS)
+        .run();
+
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .set_exact_value_fee(FEE_TOKEN, FEE_AMOUNT, TRANSFERRED_TOKEN)
+        .run();
+
+    world
+}
+
+fn set_fee_swap_pair(world: &mut ScenarioWorld) {
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .set_fee_swap_pair(SWAPPED_TOKEN, FEE_TOKEN, PAIR_ADDRESS)
+        .run();
+}
+
+fn transfer_payments(fee_payment: EsdtTokenPayment<StaticApi>) -> MultiEsdtPayment<StaticApi> {
+    let mut payments = MultiEsdtPayment::new();
+    payments.push(EsdtTokenPayment::new(
+        TRANSFERRED_TOKEN.into(),
+        0,
+        BigUint::from(100u64),
+    ));
+    payments.push(fee_payment);
+    payments
+}
+
+#[test]
+fn transfer_with_swapped_fee_test() {
+    let mut world = setup();
+    set_fee_swap_pair(&mut world);
+
+    world
+        .tx()
+        .from(USER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .transfer(
+            RECEIVER,
+            OptionalValue::<ManagedBuffer<StaticApi>>::None,
+            OptionalValue::<ManagedAddress<StaticApi>>::None,
+        )
+        .payment(transfer_payments(EsdtTokenPayment::new(
+            SWAPPED_TOKEN.into(),
+            0,
+            BigUint::from(25u64),
+        )))
+        .run();
+
+    world
+        .check_account(USER)
+        .esdt_balance(TRANSFERRED_TOKEN, 400)
+        .esdt_balance(FEE_TOKEN, 500)
+        .esdt_balance(SWAPPED_TOKEN, 480);
+    world
+        .check_account(RECEIVER)
+        .esdt_balance(TRANSFERRED_TOKEN, 100);
+    world
+        .check_account(PAIR_ADDRESS)
+        .esdt_balance(FEE_TOKEN, 1_000 - FEE_AMOUNT)
+        .esdt_balance(SWAPPED_TOKEN, 2 * FEE_AMOUNT);
+    world
+        .check_account(SC_ADDRESS)
+        .esdt_balance(FEE_TOKEN, FEE_AMOUNT)
+        .esdt_balance(SWAPPED_TOKEN, 0);
+}
+
+#[test]
+fn transfer_with_swapped_fee_slippage_test() {
+    let mut world = setup();
+    set_fee_swap_pair(&mut world);
+
+    world
+        .tx()
+        .from(USER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .transfer(
+            RECEIVER,
+            OptionalValue::<ManagedBuffer<StaticApi>>::None,
+            OptionalValue::<ManagedAddress<StaticApi>>::None,
+        )
+        .payment(transfer_payments(EsdtTokenPayment::new(
+            SWAPPED_TOKEN.into(),
+            0,
+            BigUint::from(15u64),
+        )))
+        .returns(ExpectMessage("Slippage exceeded"))
+        .run();
+
+    world
+        .check_account(USER)
+        .esdt_balance(TRANSFERRED_TOKEN, 500)
+        .esdt_balance(SWAPPED_TOKEN, 500);
+}
+
+#[test]
+fn transfer_with_unswappable_fee_test() {
+    let mut world = setup();
+
+    world
+        .tx()
+        .from(USER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .transfer(
+            RECEIVER,
+            OptionalValue::<ManagedBuffer<StaticApi>>::None,
+            OptionalValue::<ManagedAddress<StaticApi>>::None,
+        )
+        .payment(transfer_payments(EsdtTokenPayment::new(
+            SWAPPED_TOKEN.into(),
+            0,
+            BigUint::from(25u64),
+        )))
+        .returns(ExpectError(4, "Wrong fee token"))
+        .run();
+}
@@ -0,0 +1,246 @@
+use esdt_transfer_with_fee::esdt_transfer_with_fee_proxy;
+use multiversx_sc_scenario::imports::*;
+
+const CODE_PATH: MxscPath = MxscPath::new("output/esdt-transfer-with-fee.mxsc.json");
+const AGGREGATOR_MOCK_CODE_PATH: MxscPath = MxscPath::new("output/aggregator-mock.mxsc.json");
+
+const OWNER: TestAddress = TestAddress::new("owner");
+const USER: TestAddress = TestAddress::new("user");
+const RECEIVER: TestAddress = TestAddress::new("receiver");
+const SC_ADDRESS: TestSCAddress = TestSCAddress::new("esdt-transfer-with-fee");
+const AGGREGATOR_ADDRESS: TestSCAddress = TestSCAddress::new("aggregator");
+
+const TRANSFERRED_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("TKN-123456");
+const FEE_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("WEGLD-012345");
+const FEE_TOKEN_DECIMALS: u32 = 2;
+const PRICE_DECIMALS: u8 = 6;
+const MAX_PRICE_AGE: u64 = 3_600;
+const PRICE_TIMESTAMP: u64 = 1_000;
+
+// 6 decimals for the USD amounts
+const FEE_IN_USD: u64 = 10_000_000;
+const FEE_TOKEN_PRICE: u64 = 2_500_000;
+const FEE_AMOUNT: u64 = 400;
+
+mod aggregator_mock {
+    use multiversx_sc::imports::*;
+
+    #[multiversx_sc::contract]
+    pub trait AggregatorMock {
+        #[init]
+        fn init(&self) {}
+
+        #[endpoint(setLatestPriceFeed)]
+        fn set_latest_price_feed(
+            &self,
+            from: ManagedBuffer,
+            to: ManagedBuffer,
+            timestamp: u64,
+            price: BigUint,
+        ) {
+            self.price_feed(&from, &to).set((timestamp, price));
+        }
+
+        #[view(latestPriceFeed)]
+        fn latest_price_feed(
+            &self,
+            from: ManagedBuffer,
+            to: ManagedBuffer,
+        ) -> MultiValue6<u32, ManagedBuffer, ManagedBuffer, u64, BigUint, u8> {
+            let (timestamp, price) = self.price_feed(&from, &to).get();
+            (1u32, from, to, timestamp, price, 6u8).into()
+        }
+
+        #[storage_mapper("price_feed")]
+        fn price_feed(
+            &self,
+            from: &ManagedBuffer,
+            to: &ManagedBuffer,
+        ) -> SingleValueMapper<(u64, BigUint)>;
+    }
+}
+
+fn world() -> ScenarioWorld {
+    let mut blockchain = ScenarioWorld::new();
+
+    blockchain.register_contract(CODE_PATH, esdt_transfer_with_fee::ContractBuilder);
+    blockchain.register_contract(AGGREGATOR_MOCK_CODE_PATH, aggregator_mock::ContractBuilder);
+    blockchain
+}
+
+fn setup(fee_token_price: u64) -> ScenarioWorld {
+    let mut world = world();
+
+    world.account(OWNER).nonce(1);
+    world
+        .account(USER)
+        .nonce(1)
+        .esdt_balance(TRANSFERRED_TOKEN, 500)
+        .esdt_balance(FEE_TOKEN, 500);
+    world.account(RECEIVER).nonce(1);
+    world
+        .account(AGGREGATOR_ADDRESS)
+        .nonce(1)
+        .code(AGGREGATOR_MOCK_CODE_PATH)
+        .owner(OWNER);
+    world.current_block().block_timestamp(PRICE_TIMESTAMP);
+
+    world
+        .tx()
+        .from(OWNER)
+        .to(AGGREGATOR_ADDRESS)
+        .raw_call("setLatestPriceFeed")
+        .argument(&"WEGLD")
+        .argument(&"USD")
+        .argument(&PRICE_TIMESTAMP)
+        .argument(&fee_token_price)
+        .run();
+
+    world
+        .tx()
+        .from(OWNER)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .init()
+        .code(CODE_PATH)
+        .new_address(SC_ADDRESS)
+        .run();
+
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .set_oracle_priced_fee(FEE_TOKEN, FEE_TOKEN_DECIMALS, FEE_IN_USD, TRANSFERRED_TOKEN)
+        .run();
+
+    world
+}
+
+fn set_price_aggregator(world: &mut ScenarioWorld) {
+    set_price_aggregator_with_decimals(world, PRICE_DECIMALS);
+}
+
+fn set_price_aggregator_with_decimals(world: &mut ScenarioWorld, price_decimals: u8) {
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .set_price_aggregator(AGGREGATOR_ADDRESS, "USD", price_decimals, MAX_PRICE_AGE)
+        .run();
+}
+
+fn transfer_payments(fee_amount: u64) -> MultiEsdtPayment<StaticApi> {
+    let mut payments = MultiEsdtPayment::new();
+    payments.push(EsdtTokenPayment::new(
+        TRANSFERRED_TOKEN.into(),
+        0,
+        BigUint::from(100u64),
+    ));
+    payments.push(EsdtTokenPayment::new(
+        FEE_TOKEN.into(),
+        0,
+        BigUint::from(fee_amount),
+    ));
+    payments
+}
+
+fn transfer(world: &mut ScenarioWorld, fee_amount: u64, expected_error: Option<&str>) {
+    let tx = world
+        .tx()
+        .from(USER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .transfer(
+            RECEIVER,
+            OptionalValue::<ManagedBuffer<StaticApi>>::None,
+            OptionalValue::<ManagedAddress<StaticApi>>::None,
+        )
+        .payment(transfer_payments(fee_amount));
+    match expected_error {
+        Some(message) => tx.returns(ExpectError(4, message)).run(),
+        None => tx.run(),
+    }
+}
+
+#[test]
+fn oracle_priced_fee_test() {
+    let mut world = setup(FEE_TOKEN_PRICE);
+    set_price_aggregator(&mut world);
+
+    transfer(&mut world, FEE_AMOUNT, None);
+
+    world
+        .check_account(USER)
+        .esdt_balance(TRANSFERRED_TOKEN, 400)
+        .esdt_balance(FEE_TOKEN, 500 - FEE_AMOUNT);
+    world
+        .check_account(RECEIVER)
+        .esdt_balance(TRANSFERRED_TOKEN, 100);
+    world
+        .check_account(SC_ADDRESS)
+        .esdt_balance(FEE_TOKEN, FEE_AMOUNT);
+}
+
+#[test]
+fn oracle_priced_fee_rounding_test() {
+    // 10 USD at 3 USD per token is 3.3333.. tokens, rounded up to 3.34
+    let mut world = setup(3_000_000);
+    set_price_aggregator(&mut world);
+
+    transfer(
+        &mut world,
+        333,
+        Some("Mismatching payment for covering fees"),
+    );
+    transfer(&mut world, 334, None);
+
+    world.check_account(SC_ADDRESS).esdt_balance(FEE_TOKEN, 334);
+}
+
+#[test]
+fn stale_price_test() {
+    let mut world = setup(FEE_TOKEN_PRICE);
+    set_price_aggregator(&mut world);
+
+    world
+        .current_block()
+        .block_timestamp(PRICE_TIMESTAMP + MAX_PRICE_AGE + 1);
+    transfer(&mut world, FEE_AMOUNT, Some("Stale price"));
+
+    world
+        .current_block()
+        .block_timestamp(PRICE_TIMESTAMP + MAX_PRICE_AGE);
+    transfer(&mut world, FEE_AMOUNT, None);
+}
+
+#[test]
+fn price_out_of_bounds_test() {
+    let mut world = setup(FEE_TOKEN_PRICE);
+    set_price_aggregator(&mut world);
+
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .set_fee_token_price_bounds(FEE_TOKEN, 1_000_000u64, 2_000_000u64)
+        .run();
+
+    transfer(&mut world, FEE_AMOUNT, Some("Price out of bounds"));
+}
+
+#[test]
+fn price_aggregator_not_set_test() {
+    let mut world = setup(FEE_TOKEN_PRICE);
+
+    transfer(&mut world, FEE_AMOUNT, Some("Price aggregator not set"));
+}
+
+#[test]
+fn unexpected_price_decimals_test() {
+    let mut world = setup(FEE_TOKEN_PRICE);
+    set_price_aggregator_with_decimals(&mut world, 8);
+
+    transfer(&mut world, FEE_AMOUNT, Some("Unexpected price decimals"));
+}
@@ -0,0 +1,25 @@
+use multiversx_sc_scenario::*;
+
+// only the Go VM meters the gas, run with `--ignored` once `mx-scenario-go` is installed,
+// against this build and against a build of storage version 2 to compare the two layouts
+fn world() -> ScenarioWorld {
+    ScenarioWorld::vm_go()
+}
+
+#[test]
+#[ignore = "needs mx-scenario-go"]
+fn benchmark_paid_fees_1_go() {
+    world().run("scenarios/benchmark_paid_fees_1.scen.json");
+}
+
+#[test]
+#[ignore = "needs mx-scenario-go"]
+fn benchmark_paid_fees_5_go() {
+    world().run("scenarios/benchmark_paid_fees_5.scen.json");
+}
+
+#[test]
+#[ignore = "needs mx-scenario-go"]
+fn benchmark_paid_fees_50_go() {
+    world().run("scenarios/benchmark_paid_fees_50.scen.json");
+}
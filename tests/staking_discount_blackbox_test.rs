@@ -0,0 +1,257 @@
+use esdt_transfer_with_fee::esdt_transfer_with_fee_proxy;
+use multiversx_sc_scenario::imports::*;
+
+const CODE_PATH: MxscPath = MxscPath::new("output/esdt-transfer-with-fee.mxsc.json");
+const STAKING_MOCK_CODE_PATH: MxscPath = MxscPath::new("output/staking-mock.mxsc.json");
+
+const OWNER: TestAddress = TestAddress::new("owner");
+const USER: TestAddress = TestAddress::new("user");
+const RECEIVER: TestAddress = TestAddress::new("receiver");
+const SC_ADDRESS: TestSCAddress = TestSCAddress::new("esdt-transfer-with-fee");
+const STAKING_ADDRESS: TestSCAddress = TestSCAddress::new("staking");
+
+const TRANSFERRED_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("TKN-123456");
+const FEE_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("USDC-aaaaaa");
+const FEE_AMOUNT: u64 = 10;
+
+mod staking_mock {
+    use multiversx_sc::imports::*;
+
+    #[multiversx_sc::contract]
+    pub trait StakingMock {
+        #[init]
+        fn init(&self) {}
+
+        #[endpoint(setStakedAmount)]
+        fn set_staked_amount(&self, address: ManagedAddress, amount: BigUint) {
+            self.staked_amount(&address).set(amount);
+        }
+
+        #[view(getStakedAmount)]
+        #[storage_mapper("staked_amount")]
+        fn staked_amount(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+    }
+}
+
+fn world() -> ScenarioWorld {
+    let mut blockchain = ScenarioWorld::new();
+
+    blockchain.register_contract(CODE_PATH, esdt_transfer_with_fee::ContractBuilder);
+    blockchain.register_contract(STAKING_MOCK_CODE_PATH, staking_mock::ContractBuilder);
+    blockchain
+}
+
+fn setup(staked_amount: u64) -> ScenarioWorld {
+    let mut world = world();
+
+    world.account(OWNER).nonce(1);
+    world
+        .account(USER)
+        .nonce(1)
+        .esdt_balance(TRANSFERRED_TOKEN, 500)
+        .esdt_balance(FEE_TOKEN, 500);
+    world.account(RECEIVER).nonce(1);
+    world
+        .account(STAKING_ADDRESS)
+        .nonce(1)
+        .code(STAKING_MOCK_CODE_PATH)
+        .owner(OWNER);
+
+    world
+        .tx()
+        .from(OWNER)
+        .to(STAKING_ADDRESS)
+        .raw_call("setStakedAmount")
+        .argument(&USER)
+        .argument(&staked_amount)
+        .run();
+
+    world
+        .tx()
+        .from(OWNER)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .init()
+        .code(CODE_PATH)
+        .new_address(SC_ADDRESS)
+        .run();
+
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .set_staking_contract(STAKING_ADDRESS, "getStakedAmount")
+        .run();
+
+    let mut tiers = MultiValueEncoded::<StaticApi, MultiValue2<BigUint<StaticApi>, u32>>::new();
+    tiers.push(MultiValue2::from((BigUint::from(100u64), 2_000u32)));
+    tiers.push(MultiValue2::from((BigUint::from(1_000u64), 5_000u32)));
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .set_staking_discount_tiers(tiers)
+        .run();
+
+    world
+}
+
+fn set_exact_value_fee(world: &mut ScenarioWorld) {
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .set_exact_value_fee(FEE_TOKEN, FEE_AMOUNT, TRANSFERRED_TOKEN)
+        .run();
+}
+
+fn transfer_payments(fee_amount: u64) -> MultiEsdtPayment<StaticApi> {
+    let mut payments = MultiEsdtPayment::new();
+    payments.push(EsdtTokenPayment::new(
+        TRANSFERRED_TOKEN.into(),
+        0,
+        BigUint::from(100u64),
+    ));
+    if fee_amount > 0 {
+        payments.push(EsdtTokenPayment::new(
+            FEE_TOKEN.into(),
+            0,
+            BigUint::from(fee_amount),
+        ));
+    }
+    payments
+}
+
+fn transfer(world: &mut ScenarioWorld, fee_amount: u64) {
+    world
+        .tx()
+        .from(USER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .transfer(
+            RECEIVER,
+            OptionalValue::<ManagedBuffer<StaticApi>>::None,
+            OptionalValue::<ManagedAddress<StaticApi>>::None,
+        )
+        .payment(transfer_payments(fee_amount))
+        .run();
+}
+
+#[test]
+fn exact_value_fee_staking_discount_test() {
+    let mut world = setup(1_000);
+    set_exact_value_fee(&mut world);
+
+    transfer(&mut world, FEE_AMOUNT / 2);
+
+    world
+        .check_account(USER)
+        .esdt_balance(TRANSFERRED_TOKEN, 400)
+        .esdt_balance(FEE_TOKEN, 500 - FEE_AMOUNT / 2);
+    world
+        .check_account(RECEIVER)
+        .esdt_balance(TRANSFERRED_TOKEN, 100);
+    world
+        .check_account(SC_ADDRESS)
+        .esdt_balance(FEE_TOKEN, FEE_AMOUNT / 2);
+}
+
+#[test]
+fn percentage_fee_staking_discount_test() {
+    let mut world = setup(100);
+
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .set_percentage_fee(1_000u32, TRANSFERRED_TOKEN)
+        .run();
+
+    transfer(&mut world, 0);
+
+    // 10% fee with a 20% discount
+    world
+        .check_account(RECEIVER)
+        .esdt_balance(TRANSFERRED_TOKEN, 92);
+    world
+        .check_account(SC_ADDRESS)
+        .esdt_balance(TRANSFERRED_TOKEN, 8);
+}
+
+#[test]
+fn stake_below_tiers_test() {
+    let mut world = setup(99);
+    set_exact_value_fee(&mut world);
+
+    world
+        .tx()
+        .from(USER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .transfer(
+            RECEIVER,
+            OptionalValue::<ManagedBuffer<StaticApi>>::None,
+            OptionalValue::<ManagedAddress<StaticApi>>::None,
+        )
+        .payment(transfer_payments(FEE_AMOUNT / 2))
+        .returns(ExpectError(4, "Mismatching payment for covering fees"))
+        .run();
+
+    transfer(&mut world, FEE_AMOUNT);
+
+    world
+        .check_account(SC_ADDRESS)
+        .esdt_balance(FEE_TOKEN, FEE_AMOUNT);
+}
+
+#[test]
+fn invalid_staking_contract_test() {
+    let mut world = setup(0);
+
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .set_staking_contract(USER, "getStakedAmount")
+        .returns(ExpectError(4, "Invalid staking contract"))
+        .run();
+}
+
+#[test]
+fn transfer_without_fee_skips_staking_call_test() {
+    let mut world = setup(1_000);
+
+    // the staking contract is only called once a fee is charged
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .set_staking_contract(STAKING_ADDRESS, "missingView")
+        .run();
+
+    transfer(&mut world, 0);
+
+    world
+        .check_account(RECEIVER)
+        .esdt_balance(TRANSFERRED_TOKEN, 100);
+
+    set_exact_value_fee(&mut world);
+    world
+        .tx()
+        .from(USER)
+        .to(SC_ADDRESS)
+        .typed(esdt_transfer_with_fee_proxy::EsdtTransferWithFeeProxy)
+        .transfer(
+            RECEIVER,
+            OptionalValue::<ManagedBuffer<StaticApi>>::None,
+            OptionalValue::<ManagedAddress<StaticApi>>::None,
+        )
+        .payment(transfer_payments(FEE_AMOUNT))
+        .returns(ExpectStatus(1))
+        .run();
+}
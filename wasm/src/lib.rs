@@ -5,9 +5,10 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                            6
+// Upgrade:                              1
+// Endpoints:                           92
 // Async Callback (empty):               1
-// Total number of exported functions:   8
+// Total number of exported functions:  95
 
 #![no_std]
 
@@ -18,12 +19,99 @@ multiversx_sc_wasm_adapter::endpoints! {
     esdt_transfer_with_fee
     (
         init => init
+        upgrade => upgrade
         setExactValueFee => set_exact_value_fee
+        setExactValueFeeOptions => set_exact_value_fee_options
+        setOraclePricedFee => set_oracle_priced_fee
         setPercentageFee => set_percentage_fee
+        setFractionFee => set_fraction_fee
+        setRoyaltiesFee => set_royalties_fee
+        setFeeOverpaymentRefund => set_fee_overpayment_refund
         claimFees => claim_fees
         transfer => transfer
+        splitTransfer => split_transfer
+        transferWithPermit => transfer_with_permit
+        reverseTransfer => reverse_transfer
+        finalizeTransfer => finalize_transfer
         getTokenFee => token_fee
+        isFeeOverpaymentRefundEnabled => fee_overpayment_refund
+        getFeeTokens => fee_tokens
+        getStorageVersion => storage_version
         getPaidFees => paid_fees
+        getPaidFeeTokens => paid_fee_tokens
+        getPaidFeeAmount => paid_fee_amount
+        setFeeBurnShare => set_fee_burn_share
+        getFeeBurnShare => fee_burn_share
+        getBurnedFees => burned_fees
+        setFeeCredit => set_fee_credit
+        isFeeCreditEnabled => fee_credit_enabled
+        setPriceAggregator => set_price_aggregator
+        setFeeTokenPriceBounds => set_fee_token_price_bounds
+        getPriceAggregator => price_aggregator
+        getReferenceCurrency => reference_currency
+        getPriceDecimals => price_decimals
+        getMaxPriceAge => max_price_age
+        getFeeTokenPriceBounds => fee_token_price_bounds
+        setFeeRoundingMode => set_fee_rounding_mode
+        getFeeRemainder => get_fee_remainder
+        getFeeRoundingMode => fee_rounding_mode
+        getFeeStats => get_fee_stats
+        setFeeSwapPair => set_fee_swap_pair
+        removeFeeSwapPair => remove_fee_swap_pair
+        getFeeSwapPair => fee_swap_pair
+        setTreasury => set_treasury
+        setSweepThreshold => set_sweep_threshold
+        getTreasury => treasury
+        getSweepThreshold => sweep_threshold
+        setLoyaltyDiscount => set_loyalty_discount
+        registerLoyaltyTokens => register_loyalty_tokens
+        withdrawLoyaltyTokens => withdraw_loyalty_tokens
+        getLoyaltyDiscount => loyalty_discount
+        getLoyaltyTokens => loyalty_tokens
+        deposit => deposit
+        withdrawDeposits => withdraw_deposits
+        setRelayerShare => set_relayer_share
+        getDeposits => deposits
+        getPermitNonce => permit_nonce
+        getRelayerShare => relayer_share
+        addDeniedRecipients => add_denied_recipients
+        removeDeniedRecipients => remove_denied_recipients
+        setPayableRecipientRequired => set_payable_recipient_required
+        getDeniedRecipients => denied_recipients
+        isPayableRecipientRequired => payable_recipient_required
+        setRebateShare => set_rebate_share
+        claimRebates => claim_rebates
+        getClaimableRebates => get_claimable_rebates
+        getRebateShare => rebate_share
+        getRebatePool => rebate_pool
+        getRebatePoolWeight => rebate_pool_weight
+        getRebateWeights => rebate_weights
+        getRebates => rebates
+        addReferrers => add_referrers
+        removeReferrers => remove_referrers
+        setReferralShare => set_referral_share
+        claimReferralFees => claim_referral_fees
+        getReferrers => referrers
+        getReferralShare => referral_share
+        getReferralFees => referral_fees
+        setSettlementDelay => set_settlement_delay
+        setReversalFeeRefund => set_reversal_fee_refund
+        getSettlementDelay => settlement_delay
+        isReversalFeeRefundEnabled => reversal_fee_refund
+        getPendingTransfer => pending_transfers
+        setStakingContract => set_staking_contract
+        removeStakingContract => remove_staking_contract
+        setStakingDiscountTiers => set_staking_discount_tiers
+        getStakingContract => staking_contract
+        getStakingView => staking_view
+        getStakingDiscountTiers => staking_discount_tiers
+        setTokenPolicy => set_token_policy
+        addPolicyTokens => add_policy_tokens
+        removePolicyTokens => remove_policy_tokens
+        setTransferLimits => set_transfer_limits
+        getTokenPolicy => token_policy
+        getPolicyTokens => policy_tokens
+        getTransferLimits => transfer_limits
     )
 }
 